@@ -1,7 +1,62 @@
+#[cfg(any(
+    feature = "coin_cbc",
+    feature = "highs",
+    feature = "lpsolve",
+    feature = "minilp"
+))]
+use crate::{constraint, solvers::SolverModel, Expression};
 use crate::{constraint::ConstraintReference, Variable};
 
-/// A trait for solvers that support cardinality constraints
+/// A trait for solvers that support cardinality constraints.
+///
+/// SCIP supports them natively. For cbc, HiGHS, lp_solve and minilp, each variable of the
+/// constraint gets a new binary indicator variable, that has to be 1 for the variable to be
+/// non-zero, and the sum of the indicators is limited. The bounds of the variables are used
+/// to link them to their indicators, so they must be finite.
+///
+/// ```
+/// use good_lp::*;
+/// # #[cfg(any(feature = "coin_cbc", feature = "minilp", feature = "lpsolve"))] {
+/// variables! {vars: 0 <= x <= 2; 0 <= y <= 3; 0 <= z <= 1;}
+/// let mut model = vars.maximise(x + y + z).using(default_solver);
+/// model.add_cardinality_constraint(&[x, y, z], 2);
+/// let solution = model.solve()?;
+/// assert_eq!(solution.eval(x + y + z).round(), 5.);
+/// # }
+/// # Ok::<_, ResolutionError>(())
+/// ```
 pub trait CardinalityConstraintSolver {
     /// Add cardinality constraint. Constrains the number of non-zero variables from `vars` to at most `rhs`.
     fn add_cardinality_constraint(&mut self, vars: &[Variable], rhs: usize) -> ConstraintReference;
 }
+
+/// Adds a cardinality constraint to a model that does not support it natively.
+/// `vars` contains the constrained variables, with their bounds and their binary indicator.
+/// Returns the reference of the constraint on the sum of the indicators.
+///
+/// # Panics
+///
+/// If a variable has an infinite bound
+#[cfg(any(
+    feature = "coin_cbc",
+    feature = "highs",
+    feature = "lpsolve",
+    feature = "minilp"
+))]
+pub(crate) fn add_cardinality_with_indicators<M: SolverModel>(
+    model: &mut M,
+    vars: &[(Variable, (f64, f64), Variable)],
+    rhs: usize,
+) -> ConstraintReference {
+    for &(var, (min, max), indicator) in vars {
+        assert!(
+            min.is_finite() && max.is_finite(),
+            "cardinality constraints need variables with finite bounds"
+        );
+        // The variable can only be non-zero when its indicator is 1
+        model.add_constraint(constraint::leq(var, max * indicator));
+        model.add_constraint(constraint::geq(var, min * indicator));
+    }
+    let indicators: Expression = vars.iter().map(|&(_, _, indicator)| indicator).sum();
+    model.add_constraint(constraint::leq(indicators, rhs as f64))
+}
@@ -7,20 +7,72 @@ use std::ops::{Shl, Shr, Sub};
 
 /// A constraint represents a single (in)equality that must hold in the solution.
 #[derive(Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Constraint {
     /// The expression that is constrained to be null or negative
     pub(crate) expression: Expression,
-    /// if is_equality, represents expression == 0, otherwise, expression <= 0
+    /// if is_equality, represents expression == 0, otherwise, lower <= expression <= 0
     pub(crate) is_equality: bool,
+    /// The lower bound of ranged constraints, or -infinity
+    #[cfg_attr(
+        feature = "serde",
+        serde(default = "neg_infinity", skip_serializing_if = "is_neg_infinity")
+    )]
+    pub(crate) lower: f64,
+    /// An optional name, passed to the solvers and file formats that support it
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub(crate) name: Option<String>,
 }
 
 impl Constraint {
-    fn new(expression: Expression, is_equality: bool) -> Constraint {
+    pub(crate) fn new(expression: Expression, is_equality: bool) -> Constraint {
         Constraint {
             expression,
             is_equality,
+            lower: f64::NEG_INFINITY,
+            name: None,
         }
     }
+
+    /// The lower and upper bounds of the linear part of the constraint's expression
+    pub(crate) fn bounds(&self) -> (f64, f64) {
+        let upper = -self.expression.constant;
+        if self.is_equality {
+            (upper, upper)
+        } else {
+            (self.lower + upper, upper)
+        }
+    }
+
+    /// Whether the constraint has both a finite lower bound and a different upper bound
+    pub(crate) fn is_ranged(&self) -> bool {
+        !self.is_equality && self.lower > f64::NEG_INFINITY
+    }
+
+    /// Gives a name to the constraint.
+    /// The name is kept in the [ConstraintReference] returned when adding the constraint
+    /// to a problem, used by the solvers that support named constraints,
+    /// and written to LP and MPS files.
+    ///
+    /// ```
+    /// use good_lp::*;
+    /// variables!{vars: 0 <= x <= 10;}
+    /// let mut model = vars.maximise(x).using(default_solver);
+    /// let capacity = model.add_constraint(constraint!(2 * x <= 5).named("capacity"));
+    /// assert_eq!(capacity.name(), Some("capacity"));
+    /// ```
+    pub fn named<S: Into<String>>(mut self, name: S) -> Constraint {
+        self.name = Some(name.into());
+        self
+    }
+
+    /// The name of the constraint, if it was given one with [Constraint::named]
+    pub fn name(&self) -> Option<&str> {
+        self.name.as_deref()
+    }
 }
 
 impl From<Expression> for Constraint {
@@ -35,12 +87,29 @@ impl FormatWithVars for Constraint {
     where
         FUN: FnMut(&mut Formatter<'_>, Variable) -> std::fmt::Result,
     {
+        if let Some(name) = &self.name {
+            write!(f, "{}: ", name)?;
+        }
+        let (lower, upper) = self.bounds();
+        if self.is_ranged() {
+            write!(f, "{} <= ", lower)?;
+        }
         self.expression.linear.format_with(f, variable_format)?;
         write!(f, " {} ", if self.is_equality { "=" } else { "<=" })?;
-        write!(f, "{}", -self.expression.constant)
+        write!(f, "{}", upper)
     }
 }
 
+#[cfg(feature = "serde")]
+fn neg_infinity() -> f64 {
+    f64::NEG_INFINITY
+}
+
+#[cfg(feature = "serde")]
+fn is_neg_infinity(value: &f64) -> bool {
+    *value == f64::NEG_INFINITY
+}
+
 impl Debug for Constraint {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         self.format_debug(f)
@@ -62,6 +131,43 @@ pub fn geq<A, B: Sub<A, Output = Expression>>(a: A, b: B) -> Constraint {
     leq(b, a)
 }
 
+/// A ranged constraint: `lower <= expression <= upper`.
+///
+/// Solvers that support it add a single row with both bounds to the model,
+/// the others add two constraints.
+///
+/// ```
+/// use good_lp::*;
+/// variables!{vars: x; y;}
+/// let c = constraint::range(-2, x + y, 5);
+/// assert_eq!(format!("{:?}", constraint!(-2 <= x + y <= 5)), format!("{:?}", c));
+/// let solution = vars.maximise(x).using(default_solver)
+///     .with(c)
+///     .with(constraint!(y == 1))
+///     .solve()?;
+/// assert_eq!(solution.value(x), 4.);
+/// # Ok::<_, ResolutionError>(())
+/// ```
+pub fn range<L: Into<f64>, E: Into<Expression>, U: Into<f64>>(
+    lower: L,
+    expression: E,
+    upper: U,
+) -> Constraint {
+    let (lower, expression, upper) = (lower.into(), expression.into(), upper.into());
+    if upper == f64::INFINITY {
+        geq(expression, lower)
+    } else if lower == f64::NEG_INFINITY {
+        leq(expression, upper)
+    } else if lower == upper {
+        eq(expression, upper)
+    } else {
+        Constraint {
+            lower: lower - upper,
+            ..leq(expression, upper)
+        }
+    }
+}
+
 macro_rules! impl_shifts {
     ($($t:ty)*) => {$(
         impl< RHS> Shl<RHS> for $t where Self: Sub<RHS, Output=Expression> {
@@ -99,6 +205,16 @@ impl_shifts!(Expression Variable);
 /// let my_inequality = constraint!(a + b >= 3 * b - a);
 /// ```
 ///
+/// ## Create a ranged constraint
+///
+/// ```
+/// # use good_lp::*;
+/// # let mut vars = variables!();
+/// # let a = vars.add(variable().max(10));
+/// # let b = vars.add(variable());
+/// let my_range = constraint!(-2 <= a - b <= 5);
+/// ```
+///
 /// ## Full example
 ///
 /// ```
@@ -121,11 +237,30 @@ impl_shifts!(Expression Variable);
 /// ```
 #[macro_export]
 macro_rules! constraint {
+    // Look for a second comparison operator, for ranged constraints
+    (@leq [$($left:tt)*] [$($middle:tt)*] <= $($right:tt)*) => {
+        $crate::constraint::range($($left)*, $($middle)*, $($right)*)
+    };
+    (@leq [$($left:tt)*] [$($middle:tt)*]) => {
+        $crate::constraint::leq($($left)*, $($middle)*)
+    };
+    (@leq [$($left:tt)*] [$($middle:tt)*] $next:tt $($right:tt)*) => {
+        constraint!(@leq [$($left)*] [$($middle)* $next] $($right)*)
+    };
+    (@geq [$($left:tt)*] [$($middle:tt)*] >= $($right:tt)*) => {
+        $crate::constraint::range($($right)*, $($middle)*, $($left)*)
+    };
+    (@geq [$($left:tt)*] [$($middle:tt)*]) => {
+        $crate::constraint::geq($($left)*, $($middle)*)
+    };
+    (@geq [$($left:tt)*] [$($middle:tt)*] $next:tt $($right:tt)*) => {
+        constraint!(@geq [$($left)*] [$($middle)* $next] $($right)*)
+    };
     ([$($left:tt)*] <= $($right:tt)*) => {
-        $crate::constraint::leq($($left)*, $($right)*)
+        constraint!(@leq [$($left)*] [] $($right)*)
     };
     ([$($left:tt)*] >= $($right:tt)*) => {
-        $crate::constraint::geq($($left)*, $($right)*)
+        constraint!(@geq [$($left)*] [] $($right)*)
     };
     ([$($left:tt)*] == $($right:tt)*) => {
         $crate::constraint::eq($($left)*, $($right)*)
@@ -145,14 +280,32 @@ macro_rules! constraint {
 }
 
 #[derive(Clone, PartialEq, Debug)]
-/// A constraint reference contains the sequence id of the constraint within the problem
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+/// A constraint reference contains the sequence id of the constraint within the problem,
+/// and the name of the constraint if it has one
 pub struct ConstraintReference {
     pub(crate) index: usize,
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub(crate) name: Option<String>,
+}
+
+impl ConstraintReference {
+    pub(crate) fn new(index: usize, name: Option<String>) -> ConstraintReference {
+        ConstraintReference { index, name }
+    }
+
+    /// The name given to the constraint with [Constraint::named]
+    pub fn name(&self) -> Option<&str> {
+        self.name.as_deref()
+    }
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::variables;
+    use crate::{constraint, variables};
     #[test]
     fn test_leq() {
         let mut vars = variables!();
@@ -161,4 +314,26 @@ mod tests {
         let f = format!("{:?}", (3. - v0) >> v1);
         assert!(vec!["v0 + v1 <= 3", "v1 + v0 <= 3"].contains(&&*f), "{}", f)
     }
+
+    #[test]
+    fn named() {
+        let mut vars = variables!();
+        let v0 = vars.add_variable();
+        let c = (2 * v0).leq(3).named("capacity");
+        assert_eq!(c.name(), Some("capacity"));
+        assert_eq!(format!("{:?}", c), "capacity: 2 v0 <= 3");
+    }
+
+    #[test]
+    fn ranged() {
+        let mut vars = variables!();
+        let v0 = vars.add_variable();
+        let c = constraint!(-1 <= 2 * v0 + 1 <= 3);
+        assert_eq!(c.bounds(), (-2., 2.));
+        assert_eq!(format!("{:?}", c), "-2 <= 2 v0 <= 2");
+        let reversed = constraint!(3 >= 2 * v0 + 1 >= -1);
+        assert_eq!(reversed.bounds(), (-2., 2.));
+        assert!(!constraint!(-1 <= v0).is_ranged());
+        assert!(!constraint::range(1, v0, 1).is_ranged());
+    }
 }
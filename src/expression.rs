@@ -350,6 +350,52 @@ impl<E: IntoAffineExpression> std::iter::Sum<E> for Expression {
     }
 }
 
+/// Expressions are serialized with their coefficients sorted by variable,
+/// so that the serialized form does not depend on the iteration order of the hash map.
+#[cfg(feature = "serde")]
+mod serialization {
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    use super::Expression;
+    use crate::variable::Variable;
+
+    #[derive(Serialize, Deserialize)]
+    #[serde(rename = "Expression")]
+    struct SerializedExpression {
+        coefficients: Vec<(Variable, f64)>,
+        constant: f64,
+    }
+
+    impl Serialize for Expression {
+        fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            let mut coefficients: Vec<(Variable, f64)> = self
+                .linear
+                .coefficients
+                .iter()
+                .map(|(&var, &coeff)| (var, coeff))
+                .collect();
+            coefficients.sort_unstable_by_key(|(var, _)| var.index());
+            SerializedExpression {
+                coefficients,
+                constant: self.constant,
+            }
+            .serialize(serializer)
+        }
+    }
+
+    impl<'de> Deserialize<'de> for Expression {
+        fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+            let serialized = SerializedExpression::deserialize(deserializer)?;
+            let mut expr = Expression::with_capacity(serialized.coefficients.len());
+            expr.constant = serialized.constant;
+            for (var, coeff) in serialized.coefficients {
+                expr.add_mul(coeff, var);
+            }
+            Ok(expr)
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use std::collections::HashMap;
@@ -0,0 +1,1065 @@
+//! The [CPLEX LP file format](https://www.ibm.com/docs/en/icos/22.1.1?topic=cplex-lp-file-format-algebraic-representation),
+//! a human-readable text format understood by most solvers.
+
+use std::io::{self, BufRead, Write};
+
+use crate::formats::{
+    sorted_coefficients, variable_names, ParseError, ParsedProblem, ProblemBuilder, Row,
+    UniqueNames,
+};
+use crate::solvers::ObjectiveDirection;
+use crate::variable::{ProblemDescription, ProblemVariables};
+use crate::{Constraint, Expression};
+
+/// Lines longer than this are split, because some tools refuse lines longer than 255 characters
+const MAX_LINE_LENGTH: usize = 255;
+
+/// Write a problem in the LP file format.
+///
+/// The variable names are taken from [VariableDefinition::name](crate::VariableDefinition::name).
+/// Invalid characters are removed from the names, and names are made unique,
+/// so the written file is always valid.
+/// Anonymous variables are named `x{index}`, and constraints without a [name](Constraint::named)
+/// are named `c{index}`.
+///
+/// ```
+/// use good_lp::{constraint, variable, variables, formats::write_lp};
+/// use good_lp::solvers::ObjectiveDirection;
+///
+/// variables! {vars: 0 <= x <= 10; y (integer);}
+/// let objective = x + 2 * y;
+/// let constraints = vec![constraint!(x - y >= 1)];
+/// let mut out = Vec::new();
+/// write_lp(&mut out, &vars, &objective, ObjectiveDirection::Maximisation, &constraints)?;
+/// let lp = String::from_utf8(out).unwrap();
+/// assert!(lp.contains("Maximize\n obj: + x + 2 y\n"));
+/// assert!(lp.contains(" c0: - x + y <= -1\n"));
+/// # Ok::<_, std::io::Error>(())
+/// ```
+pub fn write_lp<W: Write>(
+    mut writer: W,
+    variables: &ProblemVariables,
+    objective: &Expression,
+    direction: ObjectiveDirection,
+    constraints: &[Constraint],
+) -> io::Result<()> {
+    let names = variable_names(variables, lp_name);
+    writeln!(writer, "\\ Problem written by good_lp")?;
+    writeln!(
+        writer,
+        "{}",
+        match direction {
+            ObjectiveDirection::Maximisation => "Maximize",
+            ObjectiveDirection::Minimisation => "Minimize",
+        }
+    )?;
+    write!(writer, " obj:")?;
+    write_terms(&mut writer, objective, &names, " obj:".len())?;
+    if objective.constant != 0. {
+        write!(writer, " {}", signed(objective.constant))?;
+    }
+    writeln!(writer)?;
+
+    writeln!(writer, "Subject To")?;
+    let mut row_names = UniqueNames::new(lp_name);
+    row_names.add("obj", "", 0);
+    for (index, constraint) in constraints.iter().enumerate() {
+        let name = row_names.add(constraint.name().unwrap_or(""), "c", index);
+        let (lower, upper) = constraint.bounds();
+        let mut prefix = format!(" {}:", name);
+        if constraint.is_ranged() {
+            prefix = format!("{} {} <=", prefix, number(lower));
+        }
+        write!(writer, "{}", prefix)?;
+        write_terms(&mut writer, &constraint.expression, &names, prefix.len())?;
+        let operator = if constraint.is_equality { "=" } else { "<=" };
+        writeln!(writer, " {} {}", operator, number(upper))?;
+    }
+
+    let mut bounds = Vec::new();
+    let mut generals = Vec::new();
+    let mut binaries = Vec::new();
+    for ((_, def), name) in variables.iter_variables_with_def().zip(&names) {
+        if def.is_integer && def.min == 0. && def.max == 1. {
+            binaries.push(name);
+            continue;
+        } else if def.is_integer {
+            generals.push(name);
+        }
+        let (min, max) = (def.min, def.max);
+        if min == max {
+            bounds.push(format!("{} = {}", name, number(min)));
+        } else if min == f64::NEG_INFINITY && max == f64::INFINITY {
+            bounds.push(format!("{} free", name));
+        } else if max == f64::INFINITY {
+            // 0 is the default lower bound
+            if min != 0. {
+                bounds.push(format!("{} >= {}", name, number(min)));
+            }
+        } else {
+            bounds.push(format!("{} <= {} <= {}", number(min), name, number(max)));
+        }
+    }
+    write_section(&mut writer, "Bounds", &bounds)?;
+    write_section(&mut writer, "Generals", &generals)?;
+    write_section(&mut writer, "Binaries", &binaries)?;
+    writeln!(writer, "End")
+}
+
+impl ProblemDescription {
+    /// Write the problem in the LP file format. See [write_lp].
+    /// Quadratic objectives are not supported.
+    pub fn write_lp<W: Write>(&self, writer: W) -> io::Result<()> {
+        if self.has_quadratic_objective() {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "quadratic objectives cannot be written in the LP format",
+            ));
+        }
+        write_lp(
+            writer,
+            self.variables(),
+            self.objective(),
+            self.direction(),
+            self.constraints(),
+        )
+    }
+}
+
+/// Read a problem in the LP file format.
+///
+/// The objective, constraints (including ranged constraints such as `-2 <= x + y <= 5`),
+/// bounds, general integers and binaries sections are supported.
+/// Quadratic terms, semi-continuous variables and SOS sections are not.
+///
+/// ```
+/// use good_lp::formats::read_lp;
+/// use good_lp::solvers::ObjectiveDirection;
+///
+/// let file = r"
+/// \ Comments start with a backslash
+/// Maximize
+///  obj: 3 x + 2 y
+/// Subject To
+///  capacity: x + y <= 4
+/// Bounds
+///  x <= 3
+/// Generals
+///  y
+/// End
+/// ";
+/// let problem = read_lp(file.as_bytes())?;
+/// assert_eq!(problem.direction, ObjectiveDirection::Maximisation);
+/// assert_eq!(problem.constraints[0].name(), Some("capacity"));
+/// # Ok::<_, good_lp::formats::ParseError>(())
+/// ```
+pub fn read_lp<R: BufRead>(reader: R) -> Result<ParsedProblem, ParseError> {
+    let mut tokens = vec![];
+    for (line_index, line) in reader.lines().enumerate() {
+        tokenize_line(&line?, line_index + 1, &mut tokens)?;
+    }
+    let mut parser = LpParser {
+        tokens,
+        position: 0,
+        builder: ProblemBuilder::new(),
+    };
+    parser.parse()?;
+    Ok(parser.builder.build())
+}
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+enum Section {
+    Objective(ObjectiveDirection),
+    Constraints,
+    Bounds,
+    Generals,
+    Binaries,
+    End,
+}
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+enum Operator {
+    Leq,
+    Geq,
+    Eq,
+}
+
+#[derive(Clone, PartialEq, Debug)]
+enum TokenKind {
+    Section(Section),
+    Name,
+    Number(f64),
+    Sign(f64),
+    Operator(Operator),
+    Colon,
+}
+
+#[derive(Clone, Debug)]
+struct Token {
+    kind: TokenKind,
+    text: String,
+    line: usize,
+}
+
+impl Token {
+    fn error<M: Into<String>>(&self, message: M) -> ParseError {
+        ParseError::syntax(self.line, self.text.clone(), message)
+    }
+}
+
+/// Returns the section that starts with the given words, and the number of words it uses
+fn section_keyword(words: &[&str]) -> Option<(Section, usize)> {
+    let first = words.first()?.to_ascii_lowercase();
+    let second = words.get(1).map(|w| w.to_ascii_lowercase());
+    let section = match first.as_str() {
+        "maximize" | "maximise" | "maximum" | "max" => {
+            Section::Objective(ObjectiveDirection::Maximisation)
+        }
+        "minimize" | "minimise" | "minimum" | "min" => {
+            Section::Objective(ObjectiveDirection::Minimisation)
+        }
+        "subject" | "such" => {
+            return match second.as_deref() {
+                Some("to") | Some("that") => Some((Section::Constraints, 2)),
+                _ => None,
+            }
+        }
+        "st" | "s.t." | "st." => Section::Constraints,
+        "bounds" | "bound" => Section::Bounds,
+        "generals" | "general" | "gen" | "integers" | "integer" => Section::Generals,
+        "binaries" | "binary" | "bin" => Section::Binaries,
+        "end" => Section::End,
+        _ => return None,
+    };
+    Some((section, 1))
+}
+
+const UNSUPPORTED_SECTIONS: &[&str] = &["semi-continuous", "semis", "semi", "sos"];
+
+fn is_name_char(c: u8) -> bool {
+    c.is_ascii_alphanumeric() || b"!\"#$%&()/,.;?@_`'{}|~".contains(&c)
+}
+
+fn tokenize_line(
+    line: &str,
+    line_number: usize,
+    tokens: &mut Vec<Token>,
+) -> Result<(), ParseError> {
+    let line = line.split('\\').next().unwrap_or_default();
+    let mut rest = line.trim_start();
+    let words: Vec<&str> = rest.split_whitespace().take(2).collect();
+    // Keywords are only recognized at the beginning of a line
+    if let Some((section, word_count)) = section_keyword(&words) {
+        for word in &words[..word_count] {
+            rest = rest.trim_start()[word.len()..].trim_start();
+        }
+        tokens.push(Token {
+            kind: TokenKind::Section(section),
+            text: words[..word_count].join(" "),
+            line: line_number,
+        });
+    } else if let [word] = words[..] {
+        // Section names are alone on their line
+        if UNSUPPORTED_SECTIONS.contains(&word.to_ascii_lowercase().as_str()) {
+            return Err(ParseError::syntax(line_number, word, "unsupported section"));
+        }
+    }
+    let bytes = rest.as_bytes();
+    let scan = |mut i: usize, f: fn(u8) -> bool| {
+        while i < bytes.len() && f(bytes[i]) {
+            i += 1;
+        }
+        i
+    };
+    let mut start = 0;
+    while start < rest.len() {
+        let c = bytes[start];
+        let mut end = start + 1;
+        let kind = match c {
+            c if c.is_ascii_whitespace() => {
+                start += 1;
+                continue;
+            }
+            b'+' => TokenKind::Sign(1.),
+            b'-' => TokenKind::Sign(-1.),
+            b':' => TokenKind::Colon,
+            b'<' | b'>' | b'=' => {
+                end = scan(start, |c| b"<>=".contains(&c));
+                TokenKind::Operator(match &rest[start..end] {
+                    "<" | "<=" | "=<" => Operator::Leq,
+                    ">" | ">=" | "=>" => Operator::Geq,
+                    "=" | "==" => Operator::Eq,
+                    op => return Err(ParseError::syntax(line_number, op, "unknown operator")),
+                })
+            }
+            c if c.is_ascii_digit() || c == b'.' => {
+                end = scan(start, |c| c.is_ascii_digit() || c == b'.');
+                if end < bytes.len() && (bytes[end] == b'e' || bytes[end] == b'E') {
+                    let mut exponent = end + 1;
+                    if exponent < bytes.len()
+                        && (bytes[exponent] == b'+' || bytes[exponent] == b'-')
+                    {
+                        exponent += 1;
+                    }
+                    let exponent_end = scan(exponent, |c| c.is_ascii_digit());
+                    if exponent_end > exponent {
+                        end = exponent_end;
+                    }
+                }
+                let text = &rest[start..end];
+                let value = text
+                    .parse()
+                    .map_err(|_| ParseError::syntax(line_number, text, "invalid number"))?;
+                TokenKind::Number(value)
+            }
+            b'[' => {
+                return Err(ParseError::syntax(
+                    line_number,
+                    "[",
+                    "quadratic expressions are not supported",
+                ))
+            }
+            c if is_name_char(c) => {
+                end = scan(start, is_name_char);
+                TokenKind::Name
+            }
+            _ => {
+                let text = rest[start..].chars().next().unwrap_or_default();
+                return Err(ParseError::syntax(
+                    line_number,
+                    text.to_string(),
+                    "invalid character",
+                ));
+            }
+        };
+        tokens.push(Token {
+            kind,
+            text: rest[start..end].to_string(),
+            line: line_number,
+        });
+        start = end;
+    }
+    Ok(())
+}
+
+/// A linear expression, using the indices of the variables in the problem builder
+#[derive(Default)]
+struct LinearTerms {
+    coefficients: Vec<(usize, f64)>,
+    constant: f64,
+}
+
+struct LpParser {
+    tokens: Vec<Token>,
+    position: usize,
+    builder: ProblemBuilder,
+}
+
+impl LpParser {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.position)
+    }
+
+    fn peek_kind(&self) -> Option<&TokenKind> {
+        self.peek().map(|t| &t.kind)
+    }
+
+    fn next(&mut self) -> Result<Token, ParseError> {
+        let token = self.tokens.get(self.position).cloned().ok_or_else(|| {
+            let line = self.tokens.last().map_or(1, |t| t.line);
+            ParseError::syntax(line, "", "unexpected end of file")
+        })?;
+        self.position += 1;
+        Ok(token)
+    }
+
+    fn at_section(&self) -> bool {
+        matches!(self.peek_kind(), None | Some(TokenKind::Section(_)))
+    }
+
+    fn parse(&mut self) -> Result<(), ParseError> {
+        while let Some(token) = self.peek().cloned() {
+            self.position += 1;
+            match token.kind {
+                TokenKind::Section(Section::Objective(direction)) => {
+                    self.builder.direction = direction;
+                    self.label();
+                    let terms = self.expression()?;
+                    self.builder.objective = terms.coefficients;
+                    self.builder.objective_constant = terms.constant;
+                }
+                TokenKind::Section(Section::Constraints) => {
+                    while !self.at_section() {
+                        self.constraint()?;
+                    }
+                }
+                TokenKind::Section(Section::Bounds) => {
+                    while !self.at_section() {
+                        self.bound()?;
+                    }
+                }
+                TokenKind::Section(section @ Section::Generals)
+                | TokenKind::Section(section @ Section::Binaries) => {
+                    while !self.at_section() {
+                        let token = self.next()?;
+                        if token.kind != TokenKind::Name {
+                            return Err(token.error("expected a variable name"));
+                        }
+                        let index = self.builder.variable(&token.text);
+                        let def = self.builder.definition(index);
+                        def.is_integer = true;
+                        if section == Section::Binaries {
+                            def.min = 0.;
+                            def.max = 1.;
+                        }
+                    }
+                }
+                TokenKind::Section(Section::End) => return Ok(()),
+                _ => return Err(token.error("expected a section name")),
+            }
+        }
+        Ok(())
+    }
+
+    /// Consumes a `name:` label, if there is one
+    fn label(&mut self) -> Option<String> {
+        let next_is_colon = matches!(
+            self.tokens.get(self.position + 1),
+            Some(Token {
+                kind: TokenKind::Colon,
+                ..
+            })
+        );
+        if self.peek_kind() == Some(&TokenKind::Name) && next_is_colon {
+            let name = self.tokens[self.position].text.clone();
+            self.position += 2;
+            Some(name)
+        } else {
+            None
+        }
+    }
+
+    /// Parses terms such as `3 x - y + 2`, until something that cannot continue the expression
+    fn expression(&mut self) -> Result<LinearTerms, ParseError> {
+        let mut terms = LinearTerms::default();
+        let mut first = true;
+        loop {
+            let mut sign = 1.;
+            let mut has_sign = false;
+            while let Some(&TokenKind::Sign(s)) = self.peek_kind() {
+                sign *= s;
+                has_sign = true;
+                self.position += 1;
+            }
+            if !first && !has_sign {
+                return Ok(terms);
+            }
+            let mut coefficient = None;
+            if let Some(&TokenKind::Number(n)) = self.peek_kind() {
+                coefficient = Some(n);
+                self.position += 1;
+            }
+            let starts_constraint = matches!(
+                self.tokens.get(self.position + 1),
+                Some(Token {
+                    kind: TokenKind::Colon,
+                    ..
+                })
+            );
+            match self.peek().cloned() {
+                Some(token) if token.kind == TokenKind::Name && !starts_constraint => {
+                    let index = self.builder.variable(&token.text);
+                    self.position += 1;
+                    let coeff = sign * coefficient.unwrap_or(1.);
+                    terms.coefficients.push((index, coeff));
+                }
+                _ => match coefficient {
+                    Some(n) => terms.constant += sign * n,
+                    None if has_sign || !first => {
+                        let token = self.next()?;
+                        return Err(token.error("expected a number or a variable"));
+                    }
+                    None => return Ok(terms),
+                },
+            }
+            first = false;
+        }
+    }
+
+    fn operator(&mut self) -> Result<Operator, ParseError> {
+        let token = self.next()?;
+        match token.kind {
+            TokenKind::Operator(op) => Ok(op),
+            _ => Err(token.error("expected <=, >= or =")),
+        }
+    }
+
+    /// Parses a signed number, including infinite values
+    fn value(&mut self) -> Result<f64, ParseError> {
+        let mut sign = 1.;
+        while let Some(&TokenKind::Sign(s)) = self.peek_kind() {
+            sign *= s;
+            self.position += 1;
+        }
+        let token = self.next()?;
+        match token.kind {
+            TokenKind::Number(n) => Ok(sign * n),
+            TokenKind::Name if is_infinity(&token.text) => Ok(sign * f64::INFINITY),
+            _ => Err(token.error("expected a number")),
+        }
+    }
+
+    fn constraint(&mut self) -> Result<(), ParseError> {
+        let name = self
+            .label()
+            .unwrap_or_else(|| format!("c{}", self.builder.rows.len()));
+        let first_token = self.peek().cloned();
+        let left = self.expression()?;
+        let op = self.operator()?;
+        let (coefficients, lower, upper) = if left.coefficients.is_empty() {
+            // constant <= expression [<= constant]
+            let middle = self.expression()?;
+            let bound = left.constant - middle.constant;
+            let (mut lower, mut upper) = bounds_for(op.reversed(), bound);
+            if matches!(self.peek_kind(), Some(TokenKind::Operator(_))) {
+                let second_op = self.operator()?;
+                let bound = self.value()? - middle.constant;
+                let (lower2, upper2) = bounds_for(second_op, bound);
+                lower = lower.max(lower2);
+                upper = upper.min(upper2);
+            }
+            if middle.coefficients.is_empty() {
+                let token = first_token.expect("an expression was parsed");
+                return Err(token.error("a constraint needs at least one variable"));
+            }
+            (middle.coefficients, lower, upper)
+        } else {
+            let rhs = self.value()? - left.constant;
+            let (lower, upper) = bounds_for(op, rhs);
+            (left.coefficients, lower, upper)
+        };
+        self.builder.rows.push(Row {
+            name,
+            coefficients,
+            lower,
+            upper,
+        });
+        Ok(())
+    }
+
+    fn bound(&mut self) -> Result<(), ParseError> {
+        let mut settings = vec![];
+        let starts_with_name = matches!(
+            self.peek(),
+            Some(t) if t.kind == TokenKind::Name && !is_infinity(&t.text)
+        );
+        let name = if starts_with_name {
+            // x free, x <= v, x >= v, x = v
+            let name = self.next()?;
+            if matches!(self.peek(), Some(t) if t.text.eq_ignore_ascii_case("free")) {
+                self.position += 1;
+                settings.push((Operator::Geq, f64::NEG_INFINITY));
+                settings.push((Operator::Leq, f64::INFINITY));
+            } else {
+                let op = self.operator()?;
+                settings.push((op, self.value()?));
+            }
+            name
+        } else {
+            // v <= x [<= v]
+            let value = self.value()?;
+            let op = self.operator()?;
+            settings.push((op.reversed(), value));
+            let name = self.next()?;
+            if matches!(self.peek_kind(), Some(TokenKind::Operator(_))) {
+                let op = self.operator()?;
+                settings.push((op, self.value()?));
+            }
+            name
+        };
+        if name.kind != TokenKind::Name {
+            return Err(name.error("expected a variable name"));
+        }
+        let index = self.builder.variable(&name.text);
+        let def = self.builder.definition(index);
+        for (op, value) in settings {
+            match op {
+                Operator::Leq => def.max = value,
+                Operator::Geq => def.min = value,
+                Operator::Eq => {
+                    def.min = value;
+                    def.max = value;
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Operator {
+    /// The operator to use when swapping the two sides of the comparison
+    fn reversed(self) -> Operator {
+        match self {
+            Operator::Leq => Operator::Geq,
+            Operator::Geq => Operator::Leq,
+            Operator::Eq => Operator::Eq,
+        }
+    }
+}
+
+/// The (lower, upper) bounds implied by `expression op value`
+fn bounds_for(op: Operator, value: f64) -> (f64, f64) {
+    match op {
+        Operator::Leq => (f64::NEG_INFINITY, value),
+        Operator::Geq => (value, f64::INFINITY),
+        Operator::Eq => (value, value),
+    }
+}
+
+fn is_infinity(name: &str) -> bool {
+    name.eq_ignore_ascii_case("inf") || name.eq_ignore_ascii_case("infinity")
+}
+
+fn write_section<W: Write, T: std::fmt::Display>(
+    writer: &mut W,
+    title: &str,
+    lines: &[T],
+) -> io::Result<()> {
+    if !lines.is_empty() {
+        writeln!(writer, "{}", title)?;
+        for line in lines {
+            writeln!(writer, " {}", line)?;
+        }
+    }
+    Ok(())
+}
+
+fn write_terms<W: Write>(
+    writer: &mut W,
+    expression: &Expression,
+    names: &[String],
+    mut line_length: usize,
+) -> io::Result<()> {
+    let coefficients = sorted_coefficients(expression);
+    if coefficients.is_empty() {
+        // A constraint needs at least one term
+        if let Some(name) = names.first() {
+            write!(writer, " 0 {}", name)?;
+        }
+    }
+    for (var, coeff) in coefficients {
+        let name = &names[var.index()];
+        let term = if coeff == 1. {
+            format!(" + {}", name)
+        } else if coeff == -1. {
+            format!(" - {}", name)
+        } else {
+            format!(" {} {}", signed(coeff), name)
+        };
+        if line_length + term.len() > MAX_LINE_LENGTH {
+            writeln!(writer)?;
+            line_length = 0;
+        }
+        line_length += term.len();
+        writer.write_all(term.as_bytes())?;
+    }
+    Ok(())
+}
+
+/// Format a number with an explicit sign, separated from the number by a space
+fn signed(x: f64) -> String {
+    if x.is_sign_negative() {
+        format!("- {}", number(-x))
+    } else {
+        format!("+ {}", number(x))
+    }
+}
+
+pub(crate) fn number(x: f64) -> String {
+    if x == f64::INFINITY {
+        "+inf".to_string()
+    } else if x == f64::NEG_INFINITY {
+        "-inf".to_string()
+    } else if x == 0. {
+        "0".to_string() // avoid writing -0
+    } else {
+        x.to_string()
+    }
+}
+
+/// The names that would be read as a keyword, a section or an unsupported section
+const RESERVED: &[&str] = &[
+    "bin",
+    "binaries",
+    "binary",
+    "bound",
+    "bounds",
+    "end",
+    "free",
+    "gen",
+    "general",
+    "generals",
+    "inf",
+    "infinity",
+    "integer",
+    "integers",
+    "max",
+    "maximize",
+    "maximise",
+    "maximum",
+    "min",
+    "minimize",
+    "minimise",
+    "minimum",
+    "semi",
+    "semi-continuous",
+    "semis",
+    "sos",
+    "st",
+    "s.t.",
+    "st.",
+    "subject",
+    "such",
+];
+
+/// Returns a valid LP file name, keeping only the allowed characters
+fn lp_name(name: &str) -> Option<String> {
+    let mut valid: String = name
+        .chars()
+        .filter(|&c| c.is_ascii() && is_name_char(c as u8))
+        .collect();
+    if valid.is_empty() {
+        return None;
+    }
+    if valid.starts_with(|c: char| c.is_ascii_digit() || c == '.')
+        || RESERVED.contains(&valid.to_ascii_lowercase().as_str())
+        || looks_like_exponent(&valid)
+    {
+        valid.insert(0, '_');
+    }
+    Some(valid)
+}
+
+/// Names such as `e`, `e1` or `E5x` can be read as the exponent of a number
+fn looks_like_exponent(name: &str) -> bool {
+    match name.as_bytes() {
+        [b'e' | b'E'] => true,
+        [b'e' | b'E', next, ..] => next.is_ascii_digit() || *next == b'e' || *next == b'E',
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::solvers::ObjectiveDirection;
+    use crate::{constraint, variable, variables, Expression};
+
+    use super::{read_lp, write_lp};
+    use crate::formats::ParseError;
+
+    #[test]
+    fn write_full_problem() {
+        let mut vars = variables!();
+        let x = vars.add(variable().name("x").clamp(1, 10));
+        let y = vars.add(variable().name("y").integer().min(-2));
+        let z = vars.add(variable().name("x").binary());
+        let w = vars.add(variable().max(4));
+        let objective = 3 * x - y + 0.5 * w + 7;
+        let constraints = vec![
+            constraint!(x + y + z <= 10),
+            constraint!(2 * x == w + 1),
+            constraint!(x >= 3 * z),
+        ];
+        let mut out = Vec::new();
+        write_lp(
+            &mut out,
+            &vars,
+            &objective,
+            ObjectiveDirection::Minimisation,
+            &constraints,
+        )
+        .unwrap();
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            "\\ Problem written by good_lp\n\
+             Minimize\n \
+              obj: + 3 x - y + 0.5 x3 + 7\n\
+             Subject To\n \
+              c0: + x + y + x_2 <= 10\n \
+              c1: + 2 x - x3 = 1\n \
+              c2: - x + 3 x_2 <= 0\n\
+             Bounds\n \
+              1 <= x <= 10\n \
+              y >= -2\n \
+              -inf <= x3 <= 4\n\
+             Generals\n \
+              y\n\
+             Binaries\n \
+              x_2\n\
+             End\n"
+        );
+    }
+
+    #[test]
+    fn invalid_names() {
+        let mut vars = variables!();
+        vars.add(variable().name("free"));
+        vars.add(variable().name("3 apples"));
+        vars.add(variable().name("é"));
+        let mut out = Vec::new();
+        write_lp(
+            &mut out,
+            &vars,
+            &0.into(),
+            ObjectiveDirection::Maximisation,
+            &[],
+        )
+        .unwrap();
+        let lp = String::from_utf8(out).unwrap();
+        assert!(
+            lp.contains(" _free free\n _3apples free\n x2 free\n"),
+            "{}",
+            lp
+        );
+    }
+
+    #[test]
+    fn constraint_names() {
+        variables! {vars: x;}
+        let constraints = [
+            constraint!(x <= 1).named("capacity ws3"),
+            constraint!(x <= 2),
+            constraint!(x <= 3).named("obj"),
+            constraint!(x <= 4).named("capacity_ws3"),
+        ];
+        let mut out = Vec::new();
+        write_lp(
+            &mut out,
+            &vars,
+            &x.into(),
+            ObjectiveDirection::Maximisation,
+            &constraints,
+        )
+        .unwrap();
+        let lp = String::from_utf8(out).unwrap();
+        assert!(
+            lp.contains(" capacityws3: + x <= 1\n c1: + x <= 2\n obj_2: + x <= 3\n capacity_ws3: + x <= 4\n"),
+            "{}",
+            lp
+        );
+        let parsed = read_lp(lp.as_bytes()).unwrap();
+        let names: Vec<_> = parsed.constraints.iter().map(|c| c.name()).collect();
+        assert_eq!(
+            names,
+            [
+                Some("capacityws3"),
+                Some("c1"),
+                Some("obj_2"),
+                Some("capacity_ws3")
+            ]
+        );
+    }
+
+    #[test]
+    fn ranged_constraints() {
+        variables! {vars: x; y;}
+        let constraints = [constraint!(-2 <= x + y <= 5).named("r")];
+        let mut out = Vec::new();
+        write_lp(
+            &mut out,
+            &vars,
+            &x.into(),
+            ObjectiveDirection::Maximisation,
+            &constraints,
+        )
+        .unwrap();
+        let lp = String::from_utf8(out).unwrap();
+        assert!(lp.contains(" r: -2 <= + x + y <= 5\n"), "{}", lp);
+        let parsed = read_lp(lp.as_bytes()).unwrap();
+        assert_eq!(parsed.constraints.len(), 1);
+        assert_eq!(parsed.constraints[0].bounds(), (-2., 5.));
+    }
+
+    #[test]
+    fn long_expressions_are_split() {
+        let mut vars = variables!();
+        let v = vars.add_vector(variable().min(0), 1000);
+        let objective: crate::Expression = v.iter().sum();
+        let mut out = Vec::new();
+        write_lp(
+            &mut out,
+            &vars,
+            &objective,
+            ObjectiveDirection::Maximisation,
+            &[],
+        )
+        .unwrap();
+        let lp = String::from_utf8(out).unwrap();
+        assert!(lp.lines().all(|line| line.len() <= super::MAX_LINE_LENGTH));
+        assert!(lp.contains(" + x999\n"));
+    }
+
+    #[test]
+    fn read_written_problem() {
+        let mut vars = variables!();
+        let x = vars.add(variable().name("x").clamp(1, 10));
+        let y = vars.add(variable().name("y").integer().min(-2));
+        let z = vars.add(variable().name("z").binary());
+        let w = vars.add(variable().max(4));
+        let objective = 3 * x - y + 0.5 * w + 7;
+        let constraints = vec![constraint!(x + y + z <= 10), constraint!(2 * x == w + 1)];
+        let mut out = Vec::new();
+        write_lp(
+            &mut out,
+            &vars,
+            &objective,
+            ObjectiveDirection::Maximisation,
+            &constraints,
+        )
+        .unwrap();
+        let parsed = read_lp(&out[..]).unwrap();
+        assert_eq!(parsed.direction, ObjectiveDirection::Maximisation);
+        assert_eq!(parsed.variables.len(), 4);
+        let bounds = |name: &str| {
+            let (_, def) = parsed
+                .variables
+                .iter_variables_with_def()
+                .find(|(_, def)| def.name == name)
+                .unwrap();
+            (def.min, def.max, def.is_integer)
+        };
+        assert_eq!(bounds("x"), (1., 10., false));
+        assert_eq!(bounds("y"), (-2., f64::INFINITY, true));
+        assert_eq!(bounds("z"), (0., 1., true));
+        assert_eq!(bounds("x3"), (f64::NEG_INFINITY, 4., false));
+        let x = parsed.variable("x").unwrap();
+        let y = parsed.variable("y").unwrap();
+        let w = parsed.variable("x3").unwrap();
+        assert_eq!(parsed.objective, 3 * x - y + 0.5 * w + 7);
+        let names: Vec<&str> = parsed
+            .constraints
+            .iter()
+            .map(|c| c.name().unwrap())
+            .collect();
+        assert_eq!(names, ["c0", "c1"]);
+        let c1 = &parsed.constraints[1];
+        assert!(c1.is_equality);
+        assert_eq!(c1.expression, 2 * x - w - 1);
+    }
+
+    #[test]
+    fn read_written_section_names() {
+        let names = ["max", "Minimize", "sos", "semi", "semis", "maximum", "min"];
+        let mut vars = variables!();
+        let columns: Vec<_> = names
+            .iter()
+            .map(|&name| vars.add(variable().name(name).integer().clamp(-1, 3)))
+            .collect();
+        let objective: Expression = columns.iter().sum();
+        let constraints = vec![constraint!(columns[0] + columns[1] <= 4)];
+        let mut out = Vec::new();
+        write_lp(
+            &mut out,
+            &vars,
+            &objective,
+            ObjectiveDirection::Minimisation,
+            &constraints,
+        )
+        .unwrap();
+        let parsed = read_lp(&out[..]).unwrap();
+        assert_eq!(parsed.variables.len(), names.len());
+        for name in names {
+            let var = parsed.variable(&format!("_{}", name)).unwrap();
+            let (_, def) = parsed
+                .variables
+                .iter_variables_with_def()
+                .find(|&(v, _)| v == var)
+                .unwrap();
+            assert_eq!((def.min, def.max, def.is_integer), (-1., 3., true));
+        }
+        assert_eq!(parsed.objective.linear.coefficients.len(), names.len());
+    }
+
+    #[test]
+    fn read_written_exponent_like_names() {
+        let names = ["e1", "E5x", "e", "ee2"];
+        let mut vars = variables!();
+        let columns: Vec<_> = names
+            .iter()
+            .map(|&name| vars.add(variable().name(name).clamp(0, 2)))
+            .collect();
+        let energy = vars.add(variable().name("energy").clamp(0, 2));
+        let objective: Expression = columns.iter().map(|&c| 2 * c).sum::<Expression>() + energy;
+        let constraints = vec![constraint!(3 * columns[0] - columns[1] >= 1)];
+        let mut out = Vec::new();
+        write_lp(
+            &mut out,
+            &vars,
+            &objective,
+            ObjectiveDirection::Maximisation,
+            &constraints,
+        )
+        .unwrap();
+        let parsed = read_lp(&out[..]).unwrap();
+        assert_eq!(parsed.variables.len(), names.len() + 1);
+        let coefficients = &parsed.objective.linear.coefficients;
+        for name in names {
+            let var = parsed.variable(&format!("_{}", name)).unwrap();
+            assert_eq!(coefficients[&var], 2.);
+        }
+        assert_eq!(coefficients[&parsed.variable("energy").unwrap()], 1.);
+        let expected = 1. - 3 * parsed.variable("_e1").unwrap() + parsed.variable("_E5x").unwrap();
+        assert_eq!(parsed.constraints[0].expression, expected);
+    }
+
+    #[test]
+    fn read_constraint_forms() {
+        let file = "minimize\n -x\n\
+                    st\n\
+                    r1: -2 <= x + y <= 5\n\
+                    3 x - 2 y >= 0\n\
+                    R3: 3 >= x\n\
+                    y - 2 x = -1e1\n\
+                    bounds\n\
+                    -inf <= y <= 1.5e2\n\
+                    x >= -Infinity\n\
+                    end";
+        let parsed = read_lp(file.as_bytes()).unwrap();
+        let x = parsed.variable("x").unwrap();
+        let y = parsed.variable("y").unwrap();
+        let c = &parsed.constraints;
+        assert_eq!(c.len(), 4);
+        assert_eq!(c[0].name(), Some("r1"));
+        assert_eq!(c[0].expression, x + y - 5);
+        assert_eq!(c[0].bounds(), (-2., 5.));
+        assert_eq!(c[1].name(), Some("c1"));
+        assert_eq!(c[1].expression, Expression::from(0) - (3 * x - 2 * y));
+        assert!(!c[1].is_ranged());
+        assert_eq!(c[2].expression, x - 3);
+        assert_eq!(c[3].expression, y - 2 * x + 10);
+        let defs: Vec<_> = parsed.variables.iter_variables_with_def().collect();
+        assert_eq!(
+            (defs[0].1.min, defs[0].1.max),
+            (f64::NEG_INFINITY, f64::INFINITY)
+        );
+        assert_eq!((defs[1].1.min, defs[1].1.max), (f64::NEG_INFINITY, 150.));
+    }
+
+    #[test]
+    fn errors_report_line_and_token() {
+        let file = "max\n obj: x + y\nsubject to\n c0: x + y <= 4\n c1: x * 2 <= 3\nend\n";
+        match read_lp(file.as_bytes()) {
+            Err(ParseError::Syntax { line, token, .. }) => {
+                assert_eq!((line, token.as_str()), (5, "*"))
+            }
+            Err(e) => panic!("unexpected error {}", e),
+            Ok(_) => panic!("the file should be invalid"),
+        }
+        let file = "max\n obj: x\nsubject to\n c0: x <= y\nend\n";
+        let error = read_lp(file.as_bytes()).err().unwrap();
+        assert_eq!(
+            error.to_string(),
+            "Line 4: invalid token 'y': expected a number"
+        );
+    }
+}
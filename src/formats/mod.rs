@@ -0,0 +1,282 @@
+//! Reading and writing problems in standard text file formats,
+//! to exchange them with other tools.
+//!
+//! These functions are available regardless of the solver features that are activated.
+//!
+//! Problems read from a file can be solved with any solver:
+//!
+//! ```
+//! use good_lp::{default_solver, formats::read_lp, Solution, SolverModel};
+//!
+//! let file = "Maximize\n obj: x + y\nSubject To\n c0: x + 2 y <= 4\nBounds\n x <= 3\nEnd\n";
+//! let problem = read_lp(file.as_bytes())?;
+//! let x = problem.variable("x").unwrap();
+//! let solution = problem.using(default_solver).solve()?;
+//! assert_eq!(solution.value(x), 3.);
+//! # Ok::<_, Box<dyn std::error::Error>>(())
+//! ```
+
+use std::collections::{HashMap, HashSet};
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+use std::io;
+
+use crate::constraint::{range, Constraint};
+use crate::expression::Expression;
+use crate::solvers::{ObjectiveDirection, Solver};
+use crate::variable::{ProblemDescription, ProblemVariables, VariableDefinition};
+use crate::Variable;
+
+pub mod lp;
+pub mod mps;
+
+pub use lp::{read_lp, write_lp};
+pub use mps::{read_mps, write_mps, MpsFormat};
+
+/// A problem read from a file with [read_lp] or [read_mps]
+#[derive(Clone)]
+pub struct ParsedProblem {
+    /// The variables of the problem, with the names, bounds and types found in the file
+    pub variables: ProblemVariables,
+    /// The function to optimise
+    pub objective: Expression,
+    /// Whether the objective should be maximised or minimised
+    pub direction: ObjectiveDirection,
+    /// The constraints of the problem, [named](Constraint::named) as in the file,
+    /// in the order of the file
+    pub constraints: Vec<Constraint>,
+    names: HashMap<String, Variable>,
+}
+
+impl ParsedProblem {
+    /// Finds a variable from its name in the file
+    pub fn variable(&self, name: &str) -> Option<Variable> {
+        self.names.get(name).copied()
+    }
+
+    /// Adds the problem to the given solver, to solve it
+    pub fn using<S: Solver>(self, solver: S) -> S::Model {
+        ProblemDescription::from(self).using(solver)
+    }
+}
+
+impl From<ParsedProblem> for ProblemDescription {
+    fn from(parsed: ParsedProblem) -> Self {
+        let problem = parsed
+            .variables
+            .optimise(parsed.direction, parsed.objective);
+        parsed
+            .constraints
+            .into_iter()
+            .fold(ProblemDescription::new(problem), ProblemDescription::with)
+    }
+}
+
+/// An error that occurred while reading a problem file
+#[derive(Debug)]
+pub enum ParseError {
+    /// The file could not be read
+    Io(io::Error),
+    /// The file is not valid
+    Syntax {
+        /// The number of the line that contains the error, starting from 1
+        line: usize,
+        /// The token that could not be parsed
+        token: String,
+        /// What was expected instead
+        message: String,
+    },
+}
+
+impl ParseError {
+    pub(crate) fn syntax<T: Into<String>, M: Into<String>>(
+        line: usize,
+        token: T,
+        message: M,
+    ) -> Self {
+        ParseError::Syntax {
+            line,
+            token: token.into(),
+            message: message.into(),
+        }
+    }
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ParseError::Io(e) => write!(f, "Unable to read the problem: {}", e),
+            ParseError::Syntax {
+                line,
+                token,
+                message,
+            } => write!(f, "Line {}: invalid token '{}': {}", line, token, message),
+        }
+    }
+}
+
+impl Error for ParseError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            ParseError::Io(e) => Some(e),
+            ParseError::Syntax { .. } => None,
+        }
+    }
+}
+
+impl From<io::Error> for ParseError {
+    fn from(e: io::Error) -> Self {
+        ParseError::Io(e)
+    }
+}
+
+/// Collects the contents of a file before the variables are created,
+/// because variable bounds are given after the constraints in both file formats.
+pub(crate) struct ProblemBuilder {
+    definitions: Vec<VariableDefinition>,
+    indices: HashMap<String, usize>,
+    pub(crate) objective: Vec<(usize, f64)>,
+    pub(crate) objective_constant: f64,
+    pub(crate) direction: ObjectiveDirection,
+    pub(crate) rows: Vec<Row>,
+}
+
+/// A constraint of the form `lower <= sum(coefficients) <= upper`
+pub(crate) struct Row {
+    pub(crate) name: String,
+    pub(crate) coefficients: Vec<(usize, f64)>,
+    pub(crate) lower: f64,
+    pub(crate) upper: f64,
+}
+
+impl ProblemBuilder {
+    pub(crate) fn new() -> Self {
+        ProblemBuilder {
+            definitions: vec![],
+            indices: HashMap::new(),
+            objective: vec![],
+            objective_constant: 0.,
+            direction: ObjectiveDirection::Minimisation,
+            rows: vec![],
+        }
+    }
+
+    /// The index of the variable with the given name, creating it if needed.
+    /// Both formats give new variables a lower bound of 0.
+    pub(crate) fn variable(&mut self, name: &str) -> usize {
+        if let Some(&index) = self.indices.get(name) {
+            return index;
+        }
+        let index = self.definitions.len();
+        self.definitions
+            .push(VariableDefinition::new().name(name).min(0));
+        self.indices.insert(name.to_string(), index);
+        index
+    }
+
+    pub(crate) fn variable_index(&self, name: &str) -> Option<usize> {
+        self.indices.get(name).copied()
+    }
+
+    pub(crate) fn definition(&mut self, index: usize) -> &mut VariableDefinition {
+        &mut self.definitions[index]
+    }
+
+    pub(crate) fn build(self) -> ParsedProblem {
+        let mut variables = ProblemVariables::new();
+        let vars: Vec<Variable> = self
+            .definitions
+            .into_iter()
+            .map(|def| variables.add(def))
+            .collect();
+        let expression = |coefficients: &[(usize, f64)], constant: f64| {
+            let mut expr = Expression::with_capacity(coefficients.len());
+            expr.constant = constant;
+            for &(index, coeff) in coefficients.iter().filter(|&&(_, c)| c != 0.) {
+                expr.add_mul(coeff, vars[index]);
+            }
+            expr
+        };
+        let mut constraints = Vec::with_capacity(self.rows.len());
+        for row in &self.rows {
+            // Free rows do not constrain anything
+            if row.lower == f64::NEG_INFINITY && row.upper == f64::INFINITY {
+                continue;
+            }
+            let linear = expression(&row.coefficients, 0.);
+            let c = range(row.lower, linear, row.upper);
+            constraints.push(c.named(row.name.as_str()));
+        }
+        let objective = expression(&self.objective, self.objective_constant);
+        let names = self
+            .indices
+            .into_iter()
+            .map(|(n, i)| (n, vars[i]))
+            .collect();
+        ParsedProblem {
+            variables,
+            objective,
+            direction: self.direction,
+            constraints,
+            names,
+        }
+    }
+}
+
+/// Generates valid names for the variables and constraints of a problem,
+/// making sure that no name is returned twice.
+pub(crate) struct UniqueNames {
+    used: HashSet<String>,
+    /// Returns a valid version of the given name, or None if no valid version exists
+    sanitize: fn(&str) -> Option<String>,
+}
+
+impl UniqueNames {
+    pub(crate) fn new(sanitize: fn(&str) -> Option<String>) -> Self {
+        UniqueNames {
+            used: HashSet::new(),
+            sanitize,
+        }
+    }
+
+    /// Returns a name based on `name`, or `{prefix}{index}` if `name` cannot be used.
+    pub(crate) fn add(&mut self, name: &str, prefix: &str, index: usize) -> String {
+        let stem = (self.sanitize)(name)
+            .filter(|n| !n.is_empty())
+            .unwrap_or_else(|| format!("{}{}", prefix, index));
+        let mut candidate = stem.clone();
+        let mut n = 1;
+        while self.used.contains(&candidate) {
+            n += 1;
+            candidate = format!("{}_{}", stem, n);
+        }
+        self.used.insert(candidate.clone());
+        candidate
+    }
+}
+
+/// Unique names for all the variables of a problem, in the order of their indices
+pub(crate) fn variable_names(
+    variables: &ProblemVariables,
+    sanitize: fn(&str) -> Option<String>,
+) -> Vec<String> {
+    let mut names = UniqueNames::new(sanitize);
+    variables
+        .iter_variables_with_def()
+        .map(|(var, def)| names.add(&def.name, "x", var.index()))
+        .collect()
+}
+
+/// The non-zero coefficients of an expression, sorted by variable,
+/// so that the files we write do not depend on the iteration order of the expression.
+pub(crate) fn sorted_coefficients(expression: &Expression) -> Vec<(Variable, f64)> {
+    let mut coefficients: Vec<(Variable, f64)> = expression
+        .linear
+        .coefficients
+        .iter()
+        .filter(|&(_, &coeff)| coeff != 0.)
+        .map(|(&var, &coeff)| (var, coeff))
+        .collect();
+    coefficients.sort_unstable_by_key(|(var, _)| var.index());
+    coefficients
+}
@@ -0,0 +1,784 @@
+//! The [MPS file format](https://www.ibm.com/docs/en/icos/22.1.1?topic=standard-records-in-mps-format),
+//! the oldest and most widely supported format for linear programs.
+
+use std::collections::{HashMap, HashSet};
+use std::io::{self, BufRead, Write};
+
+use crate::formats::{
+    sorted_coefficients, variable_names, ParseError, ParsedProblem, ProblemBuilder, Row,
+    UniqueNames,
+};
+use crate::solvers::ObjectiveDirection;
+use crate::variable::{ProblemDescription, ProblemVariables};
+use crate::{Constraint, Expression};
+
+/// The name of the objective row
+const OBJECTIVE: &str = "obj";
+
+/// The two variants of the MPS format
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MpsFormat {
+    /// The original format, where fields are found at fixed positions in each line.
+    /// Names cannot be longer than 8 characters, and numbers than 12 characters.
+    Fixed,
+    /// The free format, where fields are separated by spaces.
+    /// Names can have any length, but cannot contain spaces.
+    Free,
+}
+
+/// Write a problem in the MPS file format.
+///
+/// The variable names are taken from [VariableDefinition::name](crate::VariableDefinition::name).
+/// Names that cannot be represented in the chosen format
+/// (because they contain spaces, or because they are longer than 8 characters in the fixed format)
+/// are replaced by `x{index}`. The same rules apply to [constraint names](Constraint::named),
+/// and unnamed constraints are called `c{index}`. The objective is named `obj`.
+///
+/// The objective constant, if any, is written as the opposite of the right-hand side of the
+/// objective row, which is the convention used by most solvers.
+///
+/// ```
+/// use good_lp::{constraint, variables, formats::{write_mps, MpsFormat}};
+/// use good_lp::solvers::ObjectiveDirection;
+///
+/// variables! {vars: 0 <= x <= 10; y (integer);}
+/// let objective = x + 2 * y;
+/// let constraints = vec![constraint!(x - y >= 1)];
+/// let mut out = Vec::new();
+/// write_mps(&mut out, &vars, &objective, ObjectiveDirection::Maximisation, &constraints, MpsFormat::Free)?;
+/// let mps = String::from_utf8(out).unwrap();
+/// assert!(mps.contains("ROWS\n N obj\n L c0\n"));
+/// assert!(mps.contains(" UP BND x 10\n"));
+/// # Ok::<_, std::io::Error>(())
+/// ```
+pub fn write_mps<W: Write>(
+    writer: W,
+    variables: &ProblemVariables,
+    objective: &Expression,
+    direction: ObjectiveDirection,
+    constraints: &[Constraint],
+    format: MpsFormat,
+) -> io::Result<()> {
+    let sanitize = match format {
+        MpsFormat::Fixed => fixed_name,
+        MpsFormat::Free => free_name,
+    };
+    let names = variable_names(variables, sanitize);
+    let mut row_names = UniqueNames::new(sanitize);
+    row_names.add(OBJECTIVE, "", 0);
+    let rows: Vec<String> = constraints
+        .iter()
+        .enumerate()
+        .map(|(index, c)| row_names.add(c.name().unwrap_or(""), "c", index))
+        .collect();
+
+    let mut out = MpsWriter { writer, format };
+    out.line("NAME          good_lp")?;
+    if direction == ObjectiveDirection::Maximisation {
+        out.line("OBJSENSE")?;
+        out.line("    MAX")?;
+    }
+
+    out.line("ROWS")?;
+    out.record(&["N", OBJECTIVE])?;
+    for (constraint, name) in constraints.iter().zip(&rows) {
+        out.record(&[if constraint.is_equality { "E" } else { "L" }, name])?;
+    }
+
+    // MPS files store the coefficient matrix column by column
+    let mut columns = vec![Vec::new(); names.len()];
+    for (var, coeff) in sorted_coefficients(objective) {
+        columns[var.index()].push((OBJECTIVE, coeff));
+    }
+    for (constraint, name) in constraints.iter().zip(&rows) {
+        for (var, coeff) in sorted_coefficients(&constraint.expression) {
+            columns[var.index()].push((name, coeff));
+        }
+    }
+    out.line("COLUMNS")?;
+    let mut in_integer_block = false;
+    for ((_, def), (name, column)) in variables
+        .iter_variables_with_def()
+        .zip(names.iter().zip(&columns))
+    {
+        if def.is_integer != in_integer_block {
+            let marker = if def.is_integer {
+                "'INTORG'"
+            } else {
+                "'INTEND'"
+            };
+            out.record(&["", "MARKER", "'MARKER'", "", marker])?;
+            in_integer_block = def.is_integer;
+        }
+        if column.is_empty() {
+            // Variables that appear nowhere would not be declared at all
+            out.record(&["", name, OBJECTIVE, "0"])?;
+        }
+        for &(row, coeff) in column {
+            out.record(&["", name, row, &out.number(coeff)])?;
+        }
+    }
+    if in_integer_block {
+        out.record(&["", "MARKER", "'MARKER'", "", "'INTEND'"])?;
+    }
+
+    out.line("RHS")?;
+    if objective.constant != 0. {
+        out.record(&["", "RHS", OBJECTIVE, &out.number(-objective.constant)])?;
+    }
+    for (constraint, name) in constraints.iter().zip(&rows) {
+        let rhs = -constraint.expression.constant;
+        if rhs != 0. {
+            out.record(&["", "RHS", name, &out.number(rhs)])?;
+        }
+    }
+    if constraints.iter().any(Constraint::is_ranged) {
+        out.line("RANGES")?;
+        for (constraint, name) in constraints.iter().zip(&rows) {
+            if constraint.is_ranged() {
+                let (lower, upper) = constraint.bounds();
+                out.record(&["", "RNG", name, &out.number(upper - lower)])?;
+            }
+        }
+    }
+
+    out.line("BOUNDS")?;
+    for ((_, def), name) in variables.iter_variables_with_def().zip(&names) {
+        let (min, max) = (def.min, def.max);
+        if min == max {
+            out.record(&["FX", "BND", name, &out.number(min)])?;
+        } else if min == f64::NEG_INFINITY && max == f64::INFINITY {
+            out.record(&["FR", "BND", name])?;
+        } else if def.is_integer && min == 0. && max == 1. {
+            out.record(&["BV", "BND", name])?;
+        } else {
+            if min == f64::NEG_INFINITY {
+                out.record(&["MI", "BND", name])?;
+            } else if min != 0. || max < 0. {
+                // Some readers set the lower bound to -inf when the upper bound is negative
+                out.record(&["LO", "BND", name, &out.number(min)])?;
+            }
+            if max != f64::INFINITY {
+                out.record(&["UP", "BND", name, &out.number(max)])?;
+            } else if def.is_integer {
+                // Some readers give integer variables a default upper bound of 1
+                out.record(&["PL", "BND", name])?;
+            }
+        }
+    }
+    out.line("ENDATA")
+}
+
+impl ProblemDescription {
+    /// Write the problem in the MPS file format. See [write_mps].
+    /// Quadratic objectives are not supported.
+    pub fn write_mps<W: Write>(&self, writer: W, format: MpsFormat) -> io::Result<()> {
+        if self.has_quadratic_objective() {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "quadratic objectives cannot be written in the MPS format",
+            ));
+        }
+        write_mps(
+            writer,
+            self.variables(),
+            self.objective(),
+            self.direction(),
+            self.constraints(),
+            format,
+        )
+    }
+}
+
+/// Read a problem in the MPS file format.
+///
+/// Both the fixed and the free formats are accepted, as long as names do not contain spaces.
+/// Variables declared between integer markers have a default lower bound of 0
+/// and no upper bound. Ranged rows are returned as [ranged constraints](crate::constraint::range).
+///
+/// ```
+/// use good_lp::formats::read_mps;
+///
+/// let file = "NAME test
+/// ROWS
+///  N  obj
+///  G  c0
+/// COLUMNS
+///     x         obj       1
+///     x         c0        2
+/// RHS
+///     RHS       c0        4
+/// ENDATA
+/// ";
+/// let problem = read_mps(file.as_bytes())?;
+/// let x = problem.variable("x").unwrap();
+/// assert_eq!(problem.constraints.len(), 1);
+/// assert_eq!(problem.constraints[0].name(), Some("c0"));
+/// # Ok::<_, good_lp::formats::ParseError>(())
+/// ```
+pub fn read_mps<R: BufRead>(reader: R) -> Result<ParsedProblem, ParseError> {
+    let mut parser = MpsParser {
+        builder: ProblemBuilder::new(),
+        rows: HashMap::new(),
+        row_data: vec![],
+        objective: None,
+        integer: false,
+        explicit_lower: HashSet::new(),
+    };
+    let mut section = Section::Name;
+    for (line_index, line) in reader.lines().enumerate() {
+        let line = line?;
+        let line_number = line_index + 1;
+        if line.trim().is_empty() || line.starts_with('*') {
+            continue;
+        }
+        let tokens: Vec<&str> = line.split_whitespace().collect();
+        let error = |token: &str, message: &str| ParseError::syntax(line_number, token, message);
+        if !line.starts_with(char::is_whitespace) {
+            section = match tokens[0].to_ascii_uppercase().as_str() {
+                "NAME" => Section::Name,
+                "OBJSENSE" => Section::ObjSense,
+                "ROWS" => Section::Rows,
+                "COLUMNS" => Section::Columns,
+                "RHS" => Section::Rhs,
+                "RANGES" => Section::Ranges,
+                "BOUNDS" => Section::Bounds,
+                "ENDATA" => break,
+                _ => return Err(error(tokens[0], "unknown or unsupported section")),
+            };
+            // The objective sense can be written on the same line as the section name
+            if section == Section::ObjSense && tokens.len() > 1 {
+                parser
+                    .objective_sense(tokens[1])
+                    .map_err(|m| error(tokens[1], m))?;
+            }
+            continue;
+        }
+        match section {
+            Section::Name => {}
+            Section::ObjSense => parser
+                .objective_sense(tokens[0])
+                .map_err(|m| error(tokens[0], m))?,
+            Section::Rows => parser.row(&tokens).map_err(|(t, m)| error(t, m))?,
+            Section::Columns => parser.column(&tokens).map_err(|(t, m)| error(t, m))?,
+            Section::Rhs => parser.rhs(&tokens, false).map_err(|(t, m)| error(t, m))?,
+            Section::Ranges => parser.rhs(&tokens, true).map_err(|(t, m)| error(t, m))?,
+            Section::Bounds => parser.bound(&tokens).map_err(|(t, m)| error(t, m))?,
+        }
+    }
+    Ok(parser.build())
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Section {
+    Name,
+    ObjSense,
+    Rows,
+    Columns,
+    Rhs,
+    Ranges,
+    Bounds,
+}
+
+/// A row of an MPS file, before the ranges are applied
+struct MpsRow {
+    name: String,
+    kind: RowKind,
+    coefficients: Vec<(usize, f64)>,
+    rhs: f64,
+    range: Option<f64>,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum RowKind {
+    Leq,
+    Geq,
+    Eq,
+}
+
+/// The row a name refers to
+#[derive(Clone, Copy)]
+enum RowRef {
+    Objective,
+    /// An additional objective row, which is ignored
+    Free,
+    Constraint(usize),
+}
+
+/// The result of parsing a data line: the token that failed, and why
+type LineResult<'a> = Result<(), (&'a str, &'static str)>;
+
+struct MpsParser {
+    builder: ProblemBuilder,
+    rows: HashMap<String, RowRef>,
+    row_data: Vec<MpsRow>,
+    objective: Option<String>,
+    integer: bool,
+    explicit_lower: HashSet<usize>,
+}
+
+impl MpsParser {
+    fn objective_sense(&mut self, sense: &str) -> Result<(), &'static str> {
+        self.builder.direction = match sense.to_ascii_uppercase().as_str() {
+            "MAX" | "MAXIMIZE" => ObjectiveDirection::Maximisation,
+            "MIN" | "MINIMIZE" => ObjectiveDirection::Minimisation,
+            _ => return Err("expected MAX or MIN"),
+        };
+        Ok(())
+    }
+
+    fn row<'a>(&mut self, tokens: &[&'a str]) -> LineResult<'a> {
+        let (kind, name) = match tokens {
+            [kind, name] => (*kind, *name),
+            _ => return Err((tokens[0], "expected a row type and a row name")),
+        };
+        let row = match kind.to_ascii_uppercase().as_str() {
+            "N" if self.objective.is_none() => {
+                self.objective = Some(name.to_string());
+                RowRef::Objective
+            }
+            "N" => RowRef::Free,
+            kind => {
+                let kind = match kind {
+                    "L" => RowKind::Leq,
+                    "G" => RowKind::Geq,
+                    "E" => RowKind::Eq,
+                    _ => return Err((tokens[0], "expected N, L, G or E")),
+                };
+                self.row_data.push(MpsRow {
+                    name: name.to_string(),
+                    kind,
+                    coefficients: vec![],
+                    rhs: 0.,
+                    range: None,
+                });
+                RowRef::Constraint(self.row_data.len() - 1)
+            }
+        };
+        if self.rows.insert(name.to_string(), row).is_some() {
+            return Err((name, "duplicate row name"));
+        }
+        Ok(())
+    }
+
+    fn find_row<'a>(&self, name: &'a str) -> Result<RowRef, (&'a str, &'static str)> {
+        self.rows.get(name).copied().ok_or((name, "unknown row"))
+    }
+
+    fn column<'a>(&mut self, tokens: &[&'a str]) -> LineResult<'a> {
+        if tokens.len() == 3 && tokens[1] == "'MARKER'" {
+            self.integer = match tokens[2] {
+                "'INTORG'" => true,
+                "'INTEND'" => false,
+                _ => return Err((tokens[2], "expected 'INTORG' or 'INTEND'")),
+            };
+            return Ok(());
+        }
+        if tokens.len() != 3 && tokens.len() != 5 {
+            return Err((
+                tokens[0],
+                "expected a column name followed by row names and values",
+            ));
+        }
+        let column = self.builder.variable(tokens[0]);
+        if self.integer {
+            self.builder.definition(column).is_integer = true;
+        }
+        for pair in tokens[1..].chunks(2) {
+            let value = parse_number(pair[1])?;
+            match self.find_row(pair[0])? {
+                RowRef::Objective => self.builder.objective.push((column, value)),
+                RowRef::Free => {}
+                RowRef::Constraint(row) => self.row_data[row].coefficients.push((column, value)),
+            }
+        }
+        Ok(())
+    }
+
+    /// Parses a line of the RHS or RANGES section, in which the vector name is optional
+    fn rhs<'a>(&mut self, tokens: &[&'a str], is_range: bool) -> LineResult<'a> {
+        let pairs = if tokens.len() % 2 == 1 {
+            &tokens[1..]
+        } else {
+            tokens
+        };
+        for pair in pairs.chunks(2) {
+            let value = parse_number(pair[1])?;
+            match (self.find_row(pair[0])?, is_range) {
+                (RowRef::Objective, false) => self.builder.objective_constant = -value,
+                (RowRef::Constraint(row), false) => self.row_data[row].rhs = value,
+                (RowRef::Constraint(row), true) => self.row_data[row].range = Some(value),
+                (RowRef::Free, _) | (RowRef::Objective, true) => {}
+            }
+        }
+        Ok(())
+    }
+
+    fn bound<'a>(&mut self, tokens: &[&'a str]) -> LineResult<'a> {
+        let kind = tokens[0].to_ascii_uppercase();
+        let has_value = matches!(kind.as_str(), "UP" | "LO" | "FX" | "LI" | "UI");
+        // The name of the bound vector is optional
+        let (name, value) = match (has_value, tokens) {
+            (true, [_, _, name, value]) | (true, [_, name, value]) => (*name, parse_number(value)?),
+            (false, [_, _, name, ..]) | (false, [_, name]) => (*name, 0.),
+            _ => {
+                return Err((
+                    tokens[0],
+                    "expected a bound type, a column name and a value",
+                ))
+            }
+        };
+        let index = self
+            .builder
+            .variable_index(name)
+            .ok_or((name, "unknown column"))?;
+        let explicit_lower = self.explicit_lower.contains(&index);
+        let def = self.builder.definition(index);
+        match kind.as_str() {
+            "UP" | "UI" => {
+                // By convention, a negative upper bound removes the default lower bound
+                if value < 0. && def.min == 0. && !explicit_lower {
+                    def.min = f64::NEG_INFINITY;
+                }
+                def.max = value;
+            }
+            "LO" | "LI" => def.min = value,
+            "FX" => {
+                def.min = value;
+                def.max = value;
+            }
+            "FR" => {
+                def.min = f64::NEG_INFINITY;
+                def.max = f64::INFINITY;
+            }
+            "MI" => def.min = f64::NEG_INFINITY,
+            "PL" => def.max = f64::INFINITY,
+            "BV" => {
+                def.min = 0.;
+                def.max = 1.;
+                def.is_integer = true;
+            }
+            _ => return Err((tokens[0], "unknown or unsupported bound type")),
+        }
+        if matches!(kind.as_str(), "LI" | "UI") {
+            def.is_integer = true;
+        }
+        if matches!(kind.as_str(), "LO" | "LI" | "FX" | "FR" | "MI" | "BV") {
+            self.explicit_lower.insert(index);
+        }
+        Ok(())
+    }
+
+    fn build(mut self) -> ParsedProblem {
+        for row in self.row_data {
+            let rhs = row.rhs;
+            let range = row.range.map(f64::abs);
+            let (lower, upper) = match (row.kind, row.range) {
+                (RowKind::Leq, _) => (range.map_or(f64::NEG_INFINITY, |r| rhs - r), rhs),
+                (RowKind::Geq, _) => (rhs, range.map_or(f64::INFINITY, |r| rhs + r)),
+                (RowKind::Eq, Some(r)) if r < 0. => (rhs + r, rhs),
+                (RowKind::Eq, r) => (rhs, rhs + r.unwrap_or(0.)),
+            };
+            self.builder.rows.push(Row {
+                name: row.name,
+                coefficients: row.coefficients,
+                lower,
+                upper,
+            });
+        }
+        self.builder.build()
+    }
+}
+
+fn parse_number(token: &str) -> Result<f64, (&str, &'static str)> {
+    token.parse().map_err(|_| (token, "expected a number"))
+}
+
+struct MpsWriter<W> {
+    writer: W,
+    format: MpsFormat,
+}
+
+impl<W: Write> MpsWriter<W> {
+    fn line(&mut self, line: &str) -> io::Result<()> {
+        writeln!(self.writer, "{}", line)
+    }
+
+    /// Writes a data line. The fields are: code, name, name, number, name, number.
+    fn record(&mut self, fields: &[&str]) -> io::Result<()> {
+        let line = match self.format {
+            MpsFormat::Fixed => {
+                // Fields start at columns 2, 5, 15, 25, 40 and 50
+                const WIDTHS: [usize; 6] = [3, 10, 10, 15, 10, 12];
+                let mut line = String::new();
+                for (field, width) in fields.iter().zip(WIDTHS.iter()) {
+                    line.push_str(&format!(" {:<1$}", field, width - 1));
+                }
+                line.trim_end().to_string()
+            }
+            MpsFormat::Free => fields
+                .iter()
+                .filter(|f| !f.is_empty())
+                .fold(String::new(), |line, f| line + " " + f),
+        };
+        self.line(&line)
+    }
+
+    fn number(&self, x: f64) -> String {
+        let max_len = match self.format {
+            MpsFormat::Fixed => 12,
+            MpsFormat::Free => usize::MAX,
+        };
+        format_number(x, max_len)
+    }
+}
+
+/// The shortest representation of the number that fits in `max_len` characters,
+/// losing precision only if required.
+fn format_number(x: f64, max_len: usize) -> String {
+    if x == 0. {
+        return "0".to_string(); // avoid writing -0
+    }
+    let decimal = x.to_string();
+    let exponential = format!("{:e}", x);
+    let shortest = if exponential.len() < decimal.len() {
+        exponential
+    } else {
+        decimal
+    };
+    if shortest.len() <= max_len {
+        return shortest;
+    }
+    (0..16)
+        .rev()
+        .map(|precision| format!("{:.*e}", precision, x))
+        .find(|s| s.len() <= max_len)
+        .unwrap_or(shortest)
+}
+
+fn free_name(name: &str) -> Option<String> {
+    let valid: String = name.chars().filter(|c| c.is_ascii_graphic()).collect();
+    Some(valid).filter(|n| !n.is_empty())
+}
+
+fn fixed_name(name: &str) -> Option<String> {
+    free_name(name).filter(|n| n.len() <= 8)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::solvers::ObjectiveDirection;
+    use crate::{constraint, variable, variables};
+
+    use super::{format_number, read_mps, write_mps, MpsFormat};
+    use crate::formats::ParseError;
+
+    fn write(format: MpsFormat) -> String {
+        let mut vars = variables!();
+        let x = vars.add(variable().name("x").clamp(1, 10));
+        let y = vars.add(variable().name("y").integer().min(-2));
+        let z = vars.add(variable().name("a_very_long_name").binary());
+        let w = vars.add(variable().max(4));
+        vars.add(variable().name("unused").min(3));
+        let objective = 3 * x - y + 0.5 * w + 7;
+        let constraints = vec![
+            constraint!(x + y + z <= 10),
+            constraint!(2 * x == w + 1),
+            constraint!(x >= 3 * z),
+        ];
+        let mut out = Vec::new();
+        write_mps(
+            &mut out,
+            &vars,
+            &objective,
+            ObjectiveDirection::Maximisation,
+            &constraints,
+            format,
+        )
+        .unwrap();
+        String::from_utf8(out).unwrap()
+    }
+
+    #[test]
+    fn write_fixed() {
+        assert_eq!(
+            write(MpsFormat::Fixed),
+            "NAME          good_lp
+OBJSENSE
+    MAX
+ROWS
+ N  obj
+ L  c0
+ E  c1
+ L  c2
+COLUMNS
+    x         obj       3
+    x         c0        1
+    x         c1        2
+    x         c2        -1
+    MARKER    'MARKER'                 'INTORG'
+    y         obj       -1
+    y         c0        1
+    x2        c0        1
+    x2        c2        3
+    MARKER    'MARKER'                 'INTEND'
+    x3        obj       0.5
+    x3        c1        -1
+    unused    obj       0
+RHS
+    RHS       obj       -7
+    RHS       c0        10
+    RHS       c1        1
+BOUNDS
+ LO BND       x         1
+ UP BND       x         10
+ LO BND       y         -2
+ PL BND       y
+ BV BND       x2
+ MI BND       x3
+ UP BND       x3        4
+ LO BND       unused    3
+ENDATA
+"
+        );
+    }
+
+    #[test]
+    fn write_free() {
+        let mps = write(MpsFormat::Free);
+        assert!(mps.contains("\n x obj 3\n"), "{}", mps);
+        assert!(mps.contains("\n MARKER 'MARKER' 'INTORG'\n"), "{}", mps);
+        assert!(mps.contains("\n a_very_long_name c2 3\n"), "{}", mps);
+        assert!(mps.contains("\n BV BND a_very_long_name\n"), "{}", mps);
+        assert!(mps.contains("\n MI BND x3\n"), "{}", mps);
+    }
+
+    #[test]
+    fn numbers_fit_in_fixed_fields() {
+        assert_eq!(format_number(-0., 12), "0");
+        assert_eq!(format_number(0.1, 12), "0.1");
+        assert_eq!(format_number(1e30, 12), "1e30");
+        assert_eq!(format_number(1. / 3., usize::MAX), "0.3333333333333333");
+        assert_eq!(format_number(1. / 3., 12), "3.3333333e-1");
+        assert_eq!(format_number(-1. / 3., 12), "-3.333333e-1");
+    }
+
+    #[test]
+    fn read_written_problem() {
+        for format in [MpsFormat::Fixed, MpsFormat::Free] {
+            let parsed = read_mps(write(format).as_bytes()).unwrap();
+            assert_eq!(parsed.direction, ObjectiveDirection::Maximisation);
+            let defs: Vec<_> = parsed.variables.iter_variables_with_def().collect();
+            let bounds: Vec<_> = defs
+                .iter()
+                .map(|(_, d)| (d.min, d.max, d.is_integer))
+                .collect();
+            let inf = f64::INFINITY;
+            assert_eq!(
+                bounds,
+                [
+                    (1., 10., false),
+                    (-2., inf, true),
+                    (0., 1., true),
+                    (-inf, 4., false),
+                    (3., inf, false)
+                ]
+            );
+            let x = parsed.variable("x").unwrap();
+            let y = parsed.variable("y").unwrap();
+            let w = parsed.variable("x3").unwrap();
+            assert_eq!(parsed.objective, 3 * x - y + 0.5 * w + 7);
+            assert_eq!(parsed.constraints.len(), 3);
+            assert_eq!(parsed.constraints[1].name().unwrap(), "c1");
+            assert!(parsed.constraints[1].is_equality);
+            assert_eq!(parsed.constraints[1].expression, 2 * x - w - 1);
+        }
+    }
+
+    #[test]
+    fn write_ranges() {
+        variables! {vars: x; y;}
+        let constraints = [constraint!(-2 <= x + y <= 5), constraint!(x <= 3)];
+        let mut out = Vec::new();
+        write_mps(
+            &mut out,
+            &vars,
+            &x.into(),
+            ObjectiveDirection::Minimisation,
+            &constraints,
+            MpsFormat::Free,
+        )
+        .unwrap();
+        let mps = String::from_utf8(out).unwrap();
+        assert!(mps.contains("\n L c0\n"), "{}", mps);
+        assert!(mps.contains("\nRANGES\n RNG c0 7\nBOUNDS\n"), "{}", mps);
+        let parsed = read_mps(mps.as_bytes()).unwrap();
+        let bounds: Vec<_> = parsed.constraints.iter().map(|c| c.bounds()).collect();
+        assert_eq!(bounds, [(-2., 5.), (f64::NEG_INFINITY, 3.)]);
+    }
+
+    #[test]
+    fn read_ranges_and_bounds() {
+        let file = "NAME
+ROWS
+ N  cost
+ G  lim1
+ L  lim2
+ E  myeqn
+ E  myeqn2
+COLUMNS
+    x  cost  1   lim1  1
+    y  lim2  1   myeqn  -1
+    y  myeqn2  1
+RHS
+    lim1  1  lim2  4
+    myeqn2  2
+RANGES
+    RNG  lim1  3  myeqn  -2
+    RNG  myeqn2  5
+BOUNDS
+ UP BND x 4
+ UP y -1
+ENDATA
+";
+        let parsed = read_mps(file.as_bytes()).unwrap();
+        let x = parsed.variable("x").unwrap();
+        let y = parsed.variable("y").unwrap();
+        let c: Vec<_> = parsed
+            .constraints
+            .iter()
+            .map(|c| (c.name().unwrap(), c.expression.clone(), c.bounds()))
+            .collect();
+        let inf = f64::INFINITY;
+        assert_eq!(
+            c,
+            [
+                ("lim1", x - 4, (1., 4.)),
+                ("lim2", y - 4, (-inf, 4.)),
+                ("myeqn", 0 - y, (-2., 0.)),
+                ("myeqn2", y - 7, (2., 7.)),
+            ]
+        );
+        let defs: Vec<_> = parsed.variables.iter_variables_with_def().collect();
+        assert_eq!((defs[0].1.min, defs[0].1.max), (0., 4.));
+        assert_eq!((defs[1].1.min, defs[1].1.max), (f64::NEG_INFINITY, -1.));
+    }
+
+    #[test]
+    fn errors_report_line_and_token() {
+        let file = "NAME\nROWS\n N obj\nCOLUMNS\n x obj 1\n x c1 2\nENDATA\n";
+        match read_mps(file.as_bytes()) {
+            Err(ParseError::Syntax { line, token, .. }) => {
+                assert_eq!((line, token.as_str()), (6, "c1"))
+            }
+            Err(e) => panic!("unexpected error {}", e),
+            Ok(_) => panic!("the file should be invalid"),
+        }
+        let file = "NAME\nROWS\n N obj\nCOLUMNS\n x obj one\nENDATA\n";
+        let error = read_mps(file.as_bytes()).err().unwrap();
+        assert_eq!(
+            error.to_string(),
+            "Line 5: invalid token 'one': expected a number"
+        );
+    }
+}
@@ -0,0 +1,202 @@
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+
+use crate::constraint::{self, ConstraintReference};
+use crate::variable::ProblemDescription;
+use crate::{Constraint, Expression, ProblemVariables, Variable};
+
+/// A constraint that only has to hold when a binary variable is equal to 1.
+/// Created with [Variable::implies].
+///
+/// None of the solver bindings used by good_lp expose native indicator constraints,
+/// so they are reformulated with big-M constraints: for `b => expr <= rhs`, the constraint
+/// `expr <= rhs + M * (1 - b)` is added, where `M` is the largest value `expr - rhs` can take
+/// given the bounds of the variables. The bounds of the variables in the constraint
+/// must thus be finite on the side that matters.
+#[derive(Debug, Clone)]
+pub struct IndicatorConstraint {
+    binary: Variable,
+    constraint: Constraint,
+}
+
+impl Variable {
+    /// Creates an [indicator constraint](IndicatorConstraint):
+    /// `constraint` has to hold when this binary variable is equal to 1.
+    ///
+    /// ```
+    /// use good_lp::*;
+    /// variables! {vars: 0 <= x <= 10; small (binary);}
+    /// let mut problem = ProblemDescription::new(vars.maximise(x + 7 * small));
+    /// // The bonus for being small is only given when x is at most 4
+    /// problem.add_indicator_constraint(small.implies(constraint!(x <= 4)))?;
+    /// let solution = problem.using(default_solver).solve()?;
+    /// assert!((solution.value(x) - 4.).abs() < 1e-6);
+    /// # Ok::<_, Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn implies(self, constraint: Constraint) -> IndicatorConstraint {
+        IndicatorConstraint {
+            binary: self,
+            constraint,
+        }
+    }
+}
+
+impl IndicatorConstraint {
+    /// The binary variable that activates the constraint
+    pub fn binary(&self) -> Variable {
+        self.binary
+    }
+
+    /// The constraint that holds when the binary variable is 1
+    pub fn constraint(&self) -> &Constraint {
+        &self.constraint
+    }
+
+    /// The linear constraints equivalent to this indicator constraint,
+    /// with big-M values computed from the bounds of the given variables.
+    /// There is one constraint for each finite bound of the constraint that can be violated,
+    /// so an equality gives two constraints, and a constraint that always holds gives none.
+    ///
+    /// ```
+    /// use good_lp::*;
+    /// variables! {vars: 0 <= x <= 10; small (binary);}
+    /// let constraints = small.implies(constraint!(x <= 4)).to_big_m(&vars)?;
+    /// let mut model = vars.maximise(x + 7 * small).using(default_solver);
+    /// for constraint in constraints {
+    ///     model.add_constraint(constraint);
+    /// }
+    /// let solution = model.solve()?;
+    /// assert!((solution.value(x) - 4.).abs() < 1e-6);
+    /// # Ok::<_, Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn to_big_m(
+        &self,
+        variables: &ProblemVariables,
+    ) -> Result<Vec<Constraint>, IndicatorError> {
+        let binary = variables.definition(self.binary);
+        if binary.min < 0. || binary.max > 1. {
+            return Err(IndicatorError::NotBinary(self.binary));
+        }
+        let extreme_value = |expression: &Expression, sign: f64| {
+            variables
+                .extreme_value(expression, sign)
+                .map_err(IndicatorError::InfiniteBound)
+        };
+        let mut linear = self.constraint.expression.clone();
+        linear.constant = 0.;
+        let (lower, upper) = self.constraint.bounds();
+        let mut constraints = Vec::with_capacity(2);
+        let big_m = extreme_value(&linear, 1.)? - upper;
+        if big_m > 0. {
+            let expression = linear.clone() + big_m * self.binary;
+            constraints.push(constraint::leq(expression, upper + big_m));
+        }
+        if lower > f64::NEG_INFINITY {
+            let big_m = lower + extreme_value(&linear, -1.)?;
+            if big_m > 0. {
+                let expression = linear - big_m * self.binary;
+                constraints.push(constraint::geq(expression, lower - big_m));
+            }
+        }
+        if let Some(name) = &self.constraint.name {
+            let suffixes: &[&str] = match constraints.len() {
+                1 => &[""],
+                _ => &["_upper", "_lower"],
+            };
+            for (constraint, suffix) in constraints.iter_mut().zip(suffixes) {
+                constraint.name = Some(format!("{}{}", name, suffix));
+            }
+        }
+        Ok(constraints)
+    }
+}
+
+impl ProblemDescription {
+    /// Adds the [big-M reformulation](IndicatorConstraint::to_big_m) of an indicator constraint
+    /// to the problem, and returns references to the constraints that were added.
+    pub fn add_indicator_constraint(
+        &mut self,
+        indicator: IndicatorConstraint,
+    ) -> Result<Vec<ConstraintReference>, IndicatorError> {
+        let constraints = indicator.to_big_m(self.variables())?;
+        Ok(constraints
+            .into_iter()
+            .map(|c| self.add_constraint(c))
+            .collect())
+    }
+}
+
+/// An error that occurs when adding an [IndicatorConstraint]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IndicatorError {
+    /// The indicator variable can take values outside of [0, 1]
+    NotBinary(Variable),
+    /// A bound of a variable of the constraint is infinite, so no big-M value can be computed
+    InfiniteBound(Variable),
+}
+
+impl Display for IndicatorError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            IndicatorError::NotBinary(var) => write!(
+                f,
+                "The indicator variable v{} is not binary",
+                var.index()
+            ),
+            IndicatorError::InfiniteBound(var) => write!(
+                f,
+                "The variable v{} needs a finite bound to compute the big-M value of an indicator constraint",
+                var.index()
+            ),
+        }
+    }
+}
+
+impl Error for IndicatorError {}
+
+#[cfg(test)]
+mod tests {
+    use crate::{constraint, variables};
+
+    use super::IndicatorError;
+
+    #[test]
+    fn big_m() {
+        variables! {vars: 0 <= x <= 10; -2 <= y <= 3; b (binary);}
+        let constraints = b.implies(constraint!(x - y <= 4)).to_big_m(&vars).unwrap();
+        // x - y is at most 12
+        assert_eq!(constraints.len(), 1);
+        assert_eq!(constraints[0].expression, x - y + 8 * b - 12);
+        assert!(!constraints[0].is_equality);
+
+        let constraints = b
+            .implies(constraint!(x + y == 5).named("c"))
+            .to_big_m(&vars)
+            .unwrap();
+        assert_eq!(constraints.len(), 2);
+        assert_eq!(constraints[0].name(), Some("c_upper"));
+        assert_eq!(constraints[0].expression, x + y + 8 * b - 13);
+        // x + y is at least -2
+        assert_eq!(constraints[1].name(), Some("c_lower"));
+        assert_eq!(constraints[1].expression, -2 - (x + y - 7 * b));
+
+        // This constraint always holds
+        let constraints = b.implies(constraint!(x <= 10)).to_big_m(&vars).unwrap();
+        assert!(constraints.is_empty());
+    }
+
+    #[test]
+    fn errors() {
+        variables! {vars: 0 <= x; y; b (binary);}
+        assert_eq!(
+            b.implies(constraint!(x <= 4)).to_big_m(&vars).unwrap_err(),
+            IndicatorError::InfiniteBound(x)
+        );
+        // Only the bound that can violate the constraint matters
+        assert!(b.implies(constraint!(x >= 4)).to_big_m(&vars).is_ok());
+        assert_eq!(
+            y.implies(constraint!(x >= 4)).to_big_m(&vars).unwrap_err(),
+            IndicatorError::NotBinary(y)
+        );
+    }
+}
@@ -0,0 +1,145 @@
+use crate::constraint::ConstraintReference;
+use crate::solvers::{ResolutionError, Solver, SolverModel};
+use crate::variable::{ProblemDescription, ProblemVariables};
+use crate::{Expression, Variable};
+
+/// One of the two bounds of a variable
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum VariableBound {
+    /// The lower bound of the variable, set with [VariableDefinition::min](crate::VariableDefinition::min)
+    Lower(Variable),
+    /// The upper bound of the variable, set with [VariableDefinition::max](crate::VariableDefinition::max)
+    Upper(Variable),
+}
+
+/// An irreducible infeasible subsystem (IIS) of a problem:
+/// a set of constraints and variable bounds that cannot be satisfied together,
+/// but that becomes feasible as soon as any one of them is removed.
+///
+/// Returned by [ProblemDescription::infeasible_subsystem].
+#[derive(Debug, Clone, PartialEq)]
+pub struct InfeasibleSubsystem {
+    /// The constraints of the subsystem, in the order they were added to the problem
+    pub constraints: Vec<ConstraintReference>,
+    /// The variable bounds of the subsystem.
+    /// Integrality is never relaxed, so it is implicitly part of the subsystem.
+    pub bounds: Vec<VariableBound>,
+}
+
+impl ProblemDescription {
+    /// Explains why a problem is infeasible,
+    /// by finding an [irreducible infeasible subsystem](InfeasibleSubsystem) of it.
+    /// Returns `None` if the problem is feasible.
+    ///
+    /// None of the solver bindings used by good_lp expose a native IIS computation,
+    /// so this uses a deletion filter that works with any solver:
+    /// every bound and constraint is removed in turn, and stays removed if the problem
+    /// is still infeasible without it. This solves the problem once for every bound and every
+    /// constraint, with an empty objective. When several subsystems exist, one of them is returned.
+    ///
+    /// ```
+    /// use good_lp::*;
+    /// variables! {vars: 0 <= x <= 1; 0 <= y; z;}
+    /// let mut problem = ProblemDescription::new(vars.minimise(x + y + z));
+    /// let sum = problem.add_constraint(constraint!(x + y >= 5).named("sum"));
+    /// problem.add_constraint(constraint!(z >= 3));
+    /// let max_y = problem.add_constraint(constraint!(y <= 2).named("max_y"));
+    ///
+    /// let iis = problem.infeasible_subsystem(default_solver)?.expect("infeasible");
+    /// assert_eq!(iis.constraints, [sum, max_y]);
+    /// assert_eq!(iis.bounds, [VariableBound::Upper(x)]);
+    /// # Ok::<_, ResolutionError>(())
+    /// ```
+    pub fn infeasible_subsystem<S>(
+        &self,
+        mut solver: S,
+    ) -> Result<Option<InfeasibleSubsystem>, ResolutionError>
+    where
+        S: Solver,
+        S::Model: SolverModel<Error = ResolutionError>,
+    {
+        let mut bounds = Vec::new();
+        for (var, def) in self.variables().iter_variables_with_def() {
+            if def.min > f64::NEG_INFINITY {
+                bounds.push(VariableBound::Lower(var));
+            }
+            if def.max < f64::INFINITY {
+                bounds.push(VariableBound::Upper(var));
+            }
+        }
+        let mut active_bounds = vec![true; bounds.len()];
+        let mut active_constraints = vec![true; self.constraints.len()];
+        let mut is_feasible = |active_bounds: &[bool], active_constraints: &[bool]| {
+            self.is_feasible_with(&mut solver, &bounds, active_bounds, active_constraints)
+        };
+        if is_feasible(&active_bounds, &active_constraints)? {
+            return Ok(None);
+        }
+        for i in 0..active_bounds.len() {
+            active_bounds[i] = false;
+            active_bounds[i] = is_feasible(&active_bounds, &active_constraints)?;
+        }
+        for i in 0..active_constraints.len() {
+            active_constraints[i] = false;
+            active_constraints[i] = is_feasible(&active_bounds, &active_constraints)?;
+        }
+        Ok(Some(InfeasibleSubsystem {
+            constraints: self
+                .iter_constraints()
+                .zip(&active_constraints)
+                .filter(|(_, &active)| active)
+                .map(|((reference, _), _)| reference)
+                .collect(),
+            bounds: bounds
+                .into_iter()
+                .zip(active_bounds)
+                .filter(|&(_, active)| active)
+                .map(|(bound, _)| bound)
+                .collect(),
+        }))
+    }
+
+    /// Whether the problem is feasible when keeping only the given bounds and constraints
+    fn is_feasible_with<S>(
+        &self,
+        solver: &mut S,
+        bounds: &[VariableBound],
+        active_bounds: &[bool],
+        active_constraints: &[bool],
+    ) -> Result<bool, ResolutionError>
+    where
+        S: Solver,
+        S::Model: SolverModel<Error = ResolutionError>,
+    {
+        let mut definitions: Vec<_> = self
+            .variables()
+            .iter_variables_with_def()
+            .map(|(_, def)| def.clone())
+            .collect();
+        for (bound, _) in bounds.iter().zip(active_bounds).filter(|(_, &a)| !a) {
+            match *bound {
+                VariableBound::Lower(var) => definitions[var.index()].min = f64::NEG_INFINITY,
+                VariableBound::Upper(var) => definitions[var.index()].max = f64::INFINITY,
+            }
+        }
+        let mut variables = ProblemVariables::new();
+        for def in definitions {
+            variables.add(def);
+        }
+        let problem = variables.optimise(self.direction(), Expression::from(0));
+        let mut model = solver.create_model(problem);
+        for (constraint, _) in self
+            .constraints
+            .iter()
+            .zip(active_constraints)
+            .filter(|(_, &a)| a)
+        {
+            model.add_constraint(constraint.clone());
+        }
+        match model.solve() {
+            Ok(_) | Err(ResolutionError::Unbounded) => Ok(true),
+            Err(ResolutionError::Infeasible) => Ok(false),
+            Err(e) => Err(e),
+        }
+    }
+}
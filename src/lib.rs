@@ -1,5 +1,5 @@
 #![deny(missing_docs)]
-#![forbid(unsafe_code)]
+#![deny(unsafe_code)]
 #![cfg_attr(docsrs, feature(doc_cfg))]
 //!  A Linear Programming modeler that is easy to use, performant with large problems, and well-typed.
 //!
@@ -63,11 +63,27 @@
 //!
 //! Then you add constraints and solve your problem using the methods in [SolverModel].
 //!
+//! If you want to build your problem once, inspect it, or solve it with several solvers,
+//! you can also add the constraints to a solver-independent [ProblemDescription].
+//!
+//! ## Serialization
+//!
+//! When the `serde` feature is activated, variables, expressions, constraints and problems
+//! implement [serde](https://serde.rs)'s `Serialize` and `Deserialize` traits,
+//! so that models can be stored or sent to another process before being solved.
+//!
 
 pub use affine_expression_trait::IntoAffineExpression;
 pub use cardinality_constraint_solver_trait::CardinalityConstraintSolver;
 pub use constraint::Constraint;
 pub use expression::Expression;
+pub use indicator::{IndicatorConstraint, IndicatorError};
+pub use infeasibility::{InfeasibleSubsystem, VariableBound};
+pub use multi_objective::{MultiObjectiveMode, MultiObjectiveProblem};
+pub use piecewise_linear::PiecewiseLinear;
+pub use quadratic_expression::{IntoObjective, QuadraticExpression};
+pub use sensitivity::{SensitivityRange, SensitivityReport, SolutionWithSensitivity};
+pub use soft_constraint::{SoftConstraint, SoftConstraintReference};
 #[cfg_attr(docsrs, doc(cfg(feature = "minilp")))]
 #[cfg(feature = "coin_cbc")]
 pub use solvers::coin_cbc::coin_cbc;
@@ -110,10 +126,12 @@ pub use solvers::scip::scip;
 #[cfg(feature = "scip")]
 pub use solvers::scip::scip as default_solver;
 pub use solvers::{
-    solver_name, DualValues, ModelWithSOS1, ResolutionError, Solution, SolutionWithDual, Solver,
-    SolverModel, StaticSolver, WithMipGap,
+    solver_name, DualValues, ModelWithSOS1, ModelWithSOS2, ModifiableModel, ResolutionError,
+    Solution, SolutionInfo, SolutionStatus, SolutionWithDual, SolutionWithReducedCosts, Solver,
+    SolverModel, StaticSolver, WithInitialSolution, WithMipGap, WithRandomSeed, WithThreads,
+    WithTimeLimit, WithVerbosity,
 };
-pub use variable::{variable, ProblemVariables, Variable, VariableDefinition};
+pub use variable::{variable, ProblemDescription, ProblemVariables, Variable, VariableDefinition};
 
 #[cfg(not(any(
     feature = "coin_cbc",
@@ -152,5 +170,15 @@ pub mod variable;
 mod affine_expression_trait;
 mod cardinality_constraint_solver_trait;
 pub mod constraint;
+pub mod formats;
+mod indicator;
+mod infeasibility;
+pub mod linearization;
+pub mod logic;
+mod multi_objective;
+mod piecewise_linear;
+mod quadratic_expression;
+mod sensitivity;
+mod soft_constraint;
 pub mod solvers;
 mod variables_macro;
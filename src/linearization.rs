@@ -0,0 +1,242 @@
+//! Linear formulations of common non-linear functions:
+//! [absolute values](abs), [maximums](max), [minimums](min) and [products of binaries](and).
+//!
+//! Each function creates a new variable that is equal to the result,
+//! and returns it with the constraints that define it. These constraints have to be added
+//! to the model for the variable to take the right value.
+//! When the function cannot be expressed with linear constraints alone, binary variables are
+//! created, and big-M values are computed from the bounds of the variables: these bounds
+//! must be finite.
+//!
+//! ```
+//! use good_lp::*;
+//! variables! {vars: 0 <= x <= 10; 0 <= y <= 10;}
+//! // The distance between x and y
+//! let (distance, constraints) = linearization::abs(&mut vars, x - y)?;
+//! let mut model = vars.maximise(distance).using(default_solver);
+//! for constraint in constraints {
+//!     model.add_constraint(constraint);
+//! }
+//! let solution = model.with(constraint!(x + y == 12)).solve()?;
+//! assert!((solution.value(distance) - 8.).abs() < 1e-6);
+//! # Ok::<_, Box<dyn std::error::Error>>(())
+//! ```
+
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+
+use crate::constraint;
+use crate::variable::variable;
+use crate::{Constraint, Expression, IntoAffineExpression, ProblemVariables, Variable};
+
+/// The absolute value of an expression.
+///
+/// No binary variable is needed when the sign of the expression is known from the bounds
+/// of its variables. Otherwise, a binary variable selects the sign, and the bounds must be finite.
+pub fn abs<E: IntoAffineExpression>(
+    variables: &mut ProblemVariables,
+    expression: E,
+) -> Result<(Variable, Vec<Constraint>), LinearizationError> {
+    let expression = expression.into_expression();
+    let (min, max) = range(variables, &expression)?;
+    let result = variables.add(variable().clamp(0, max.max(-min)));
+    let constraints = if min >= 0. {
+        vec![constraint::eq(result, expression)]
+    } else if max <= 0. {
+        vec![constraint::eq(result, -expression)]
+    } else {
+        // positive is 1 when the expression is positive
+        let positive = variables.add(variable().binary());
+        vec![
+            constraint::geq(result, expression.clone()),
+            constraint::geq(result, -expression.clone()),
+            constraint::leq(result, expression.clone() - 2. * min * (1 - positive)),
+            constraint::leq(result, 2. * max * positive - expression),
+        ]
+    };
+    Ok((result, constraints))
+}
+
+/// The maximum of several expressions.
+///
+/// The expressions that can never be larger than another one, given the bounds of the variables,
+/// are ignored. If a single expression remains, no binary variable is needed.
+/// Otherwise, one binary variable per expression selects the one that is the maximum.
+///
+/// # Panics
+///
+/// If there is no expression.
+pub fn max<E: IntoAffineExpression, I: IntoIterator<Item = E>>(
+    variables: &mut ProblemVariables,
+    expressions: I,
+) -> Result<(Variable, Vec<Constraint>), LinearizationError> {
+    extremum(variables, expressions, 1.)
+}
+
+/// The minimum of several expressions. See [max].
+///
+/// # Panics
+///
+/// If there is no expression.
+pub fn min<E: IntoAffineExpression, I: IntoIterator<Item = E>>(
+    variables: &mut ProblemVariables,
+    expressions: I,
+) -> Result<(Variable, Vec<Constraint>), LinearizationError> {
+    extremum(variables, expressions, -1.)
+}
+
+/// The maximum of the expressions if `sign` is 1, the minimum if it is -1
+fn extremum<E: IntoAffineExpression, I: IntoIterator<Item = E>>(
+    variables: &mut ProblemVariables,
+    expressions: I,
+    sign: f64,
+) -> Result<(Variable, Vec<Constraint>), LinearizationError> {
+    // With the sign applied, the problem is always to compute a maximum
+    let mut candidates = Vec::new();
+    for expression in expressions {
+        let expression = sign * expression.into_expression();
+        let (min, max) = range(variables, &expression)?;
+        candidates.push((expression, min, max));
+    }
+    assert!(
+        !candidates.is_empty(),
+        "no expression to compute an extremum of"
+    );
+    let largest_min = candidates
+        .iter()
+        .map(|&(_, min, _)| min)
+        .fold(f64::NEG_INFINITY, f64::max);
+    // An expression can be the maximum only if it can reach the largest lower bound
+    candidates.retain(|&(_, _, max)| max >= largest_min);
+    let largest_max = candidates
+        .iter()
+        .map(|&(_, _, max)| max)
+        .fold(f64::NEG_INFINITY, f64::max);
+    let (result_min, result_max) = if sign > 0. {
+        (largest_min, largest_max)
+    } else {
+        (-largest_max, -largest_min)
+    };
+    let result = variables.add(variable().clamp(result_min, result_max));
+    let signed_result = sign * result;
+    if candidates.len() == 1 {
+        let (expression, _, _) = candidates.pop().unwrap();
+        return Ok((result, vec![constraint::eq(signed_result, expression)]));
+    }
+    let selected = variables.add_vector(variable().binary(), candidates.len());
+    let mut constraints = Vec::with_capacity(2 * candidates.len() + 1);
+    constraints.push(constraint::eq(selected.iter().sum::<Expression>(), 1));
+    for ((expression, min, _), &selected) in candidates.into_iter().zip(&selected) {
+        let big_m = largest_max - min;
+        constraints.push(constraint::geq(signed_result.clone(), expression.clone()));
+        constraints.push(constraint::leq(
+            signed_result.clone(),
+            expression + big_m * (1 - selected),
+        ));
+    }
+    Ok((result, constraints))
+}
+
+/// The product of two binary variables, which is 1 when both are 1.
+/// It does not need any new binary variable.
+pub fn and(
+    variables: &mut ProblemVariables,
+    a: Variable,
+    b: Variable,
+) -> Result<(Variable, Vec<Constraint>), LinearizationError> {
+    for var in [a, b] {
+        let def = variables.definition(var);
+        if !def.is_integer || def.min < 0. || def.max > 1. {
+            return Err(LinearizationError::NotBinary(var));
+        }
+    }
+    let result = variables.add(variable().clamp(0, 1));
+    let constraints = vec![
+        constraint::leq(result, a),
+        constraint::leq(result, b),
+        constraint::geq(result, a + b - 1),
+    ];
+    Ok((result, constraints))
+}
+
+/// The smallest and the largest values of an expression
+fn range(
+    variables: &ProblemVariables,
+    expression: &Expression,
+) -> Result<(f64, f64), LinearizationError> {
+    let max = variables.extreme_value(expression, 1.);
+    let min = variables.extreme_value(expression, -1.);
+    match (min, max) {
+        (Ok(min), Ok(max)) => Ok((-min, max)),
+        (Err(var), _) | (_, Err(var)) => Err(LinearizationError::InfiniteBound(var)),
+    }
+}
+
+/// An error that occurs when linearizing a function
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LinearizationError {
+    /// A variable that should be binary can take other values
+    NotBinary(Variable),
+    /// A bound of a variable is infinite, so no big-M value can be computed
+    InfiniteBound(Variable),
+}
+
+impl Display for LinearizationError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            LinearizationError::NotBinary(var) => {
+                write!(f, "The variable v{} is not binary", var.index())
+            }
+            LinearizationError::InfiniteBound(var) => write!(
+                f,
+                "The variable v{} needs finite bounds to compute a big-M value",
+                var.index()
+            ),
+        }
+    }
+}
+
+impl Error for LinearizationError {}
+
+#[cfg(test)]
+mod tests {
+    use crate::variables;
+
+    use super::LinearizationError;
+
+    #[test]
+    fn known_sign() {
+        variables! {vars: 1 <= x <= 10; 0 <= y <= 3;}
+        let (_, constraints) = super::abs(&mut vars, x - y).unwrap();
+        // x - y can be negative
+        assert_eq!(constraints.len(), 4);
+        let (_, constraints) = super::abs(&mut vars, y - x - 3).unwrap();
+        assert_eq!(constraints.len(), 1);
+        assert_eq!(vars.len(), 5);
+    }
+
+    #[test]
+    fn dominated_expressions() {
+        variables! {vars: 5 <= x <= 10; 0 <= y <= 3; 0 <= z <= 8;}
+        // y is always smaller than x
+        let (_, constraints) = super::max(&mut vars, [x, y]).unwrap();
+        assert_eq!(constraints.len(), 1);
+        let (_, constraints) = super::max(&mut vars, [x, y, z]).unwrap();
+        assert_eq!(constraints.len(), 5);
+        let (_, constraints) = super::min(&mut vars, [x, y]).unwrap();
+        assert_eq!(constraints.len(), 1);
+    }
+
+    #[test]
+    fn errors() {
+        variables! {vars: 0 <= x; b (binary); 0 <= c <= 1;}
+        assert_eq!(
+            super::abs(&mut vars, x - 1).unwrap_err(),
+            LinearizationError::InfiniteBound(x)
+        );
+        assert_eq!(
+            super::and(&mut vars, b, c).unwrap_err(),
+            LinearizationError::NotBinary(c)
+        );
+    }
+}
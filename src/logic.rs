@@ -0,0 +1,420 @@
+//! Logical formulas over binary variables, and their translation to linear constraints.
+//!
+//! A [Formula] is built from binary variables with the functions of this module
+//! and the `!`, `&`, `|` and `^` operators. It can then be enforced with
+//! [Formula::constraints], or turned into an expression equal to its truth value
+//! with [Formula::to_expression].
+//!
+//! ```
+//! use good_lp::*;
+//! use good_lp::logic::{implies, not, Formula};
+//! variables! {vars: shift_a (binary); shift_b (binary); overtime (binary);}
+//! // If shift A is worked, then shift B is not, unless overtime is allowed
+//! let rule = implies(Formula::from(shift_a) & not(overtime), not(shift_b));
+//! let constraints = rule.constraints(&mut vars)?;
+//! let mut model = vars
+//!     .maximise(shift_a + shift_b - 0.5 * overtime)
+//!     .using(default_solver);
+//! for constraint in constraints {
+//!     model.add_constraint(constraint);
+//! }
+//! let solution = model.solve()?;
+//! assert_eq!(solution.value(overtime).round(), 1.);
+//! # Ok::<_, Box<dyn std::error::Error>>(())
+//! ```
+
+use std::ops::{BitAnd, BitOr, BitXor, Not};
+
+use crate::constraint;
+use crate::linearization::LinearizationError;
+use crate::variable::variable;
+use crate::{Constraint, Expression, ProblemVariables, Variable};
+
+/// A logical formula over binary variables
+#[derive(Debug, Clone, PartialEq)]
+pub enum Formula {
+    /// True when the binary variable is 1
+    Var(Variable),
+    /// True when the formula is false
+    Not(Box<Formula>),
+    /// True when all the formulas are true
+    And(Vec<Formula>),
+    /// True when at least one of the formulas is true
+    Or(Vec<Formula>),
+    /// True when exactly one of the two formulas is true
+    Xor(Box<Formula>, Box<Formula>),
+    /// True when the first formula is false or the second one is true
+    Implies(Box<Formula>, Box<Formula>),
+    /// True when both formulas are true, or both are false
+    Equivalent(Box<Formula>, Box<Formula>),
+    /// True when at least the given number of formulas are true
+    AtLeast(usize, Vec<Formula>),
+    /// True when at most the given number of formulas are true
+    AtMost(usize, Vec<Formula>),
+    /// True when exactly the given number of formulas are true
+    Exactly(usize, Vec<Formula>),
+}
+
+impl From<Variable> for Formula {
+    fn from(variable: Variable) -> Self {
+        Formula::Var(variable)
+    }
+}
+
+/// The negation of a formula
+pub fn not<F: Into<Formula>>(formula: F) -> Formula {
+    Formula::Not(Box::new(formula.into()))
+}
+
+/// The conjunction of formulas
+pub fn and<F: Into<Formula>, I: IntoIterator<Item = F>>(formulas: I) -> Formula {
+    Formula::And(formulas.into_iter().map(Into::into).collect())
+}
+
+/// The disjunction of formulas
+pub fn or<F: Into<Formula>, I: IntoIterator<Item = F>>(formulas: I) -> Formula {
+    Formula::Or(formulas.into_iter().map(Into::into).collect())
+}
+
+/// The exclusive or of two formulas
+pub fn xor<A: Into<Formula>, B: Into<Formula>>(a: A, b: B) -> Formula {
+    Formula::Xor(Box::new(a.into()), Box::new(b.into()))
+}
+
+/// The implication of `then` by `condition`
+pub fn implies<A: Into<Formula>, B: Into<Formula>>(condition: A, then: B) -> Formula {
+    Formula::Implies(Box::new(condition.into()), Box::new(then.into()))
+}
+
+/// The equivalence of two formulas
+pub fn equivalent<A: Into<Formula>, B: Into<Formula>>(a: A, b: B) -> Formula {
+    Formula::Equivalent(Box::new(a.into()), Box::new(b.into()))
+}
+
+/// True when at least `k` of the formulas are true
+pub fn at_least<F: Into<Formula>, I: IntoIterator<Item = F>>(k: usize, formulas: I) -> Formula {
+    Formula::AtLeast(k, formulas.into_iter().map(Into::into).collect())
+}
+
+/// True when at most `k` of the formulas are true
+pub fn at_most<F: Into<Formula>, I: IntoIterator<Item = F>>(k: usize, formulas: I) -> Formula {
+    Formula::AtMost(k, formulas.into_iter().map(Into::into).collect())
+}
+
+/// True when exactly `k` of the formulas are true
+pub fn exactly<F: Into<Formula>, I: IntoIterator<Item = F>>(k: usize, formulas: I) -> Formula {
+    Formula::Exactly(k, formulas.into_iter().map(Into::into).collect())
+}
+
+impl Not for Formula {
+    type Output = Formula;
+
+    fn not(self) -> Formula {
+        not(self)
+    }
+}
+
+impl<F: Into<Formula>> BitAnd<F> for Formula {
+    type Output = Formula;
+
+    fn bitand(self, rhs: F) -> Formula {
+        match self {
+            Formula::And(mut formulas) => {
+                formulas.push(rhs.into());
+                Formula::And(formulas)
+            }
+            formula => and([formula, rhs.into()]),
+        }
+    }
+}
+
+impl<F: Into<Formula>> BitOr<F> for Formula {
+    type Output = Formula;
+
+    fn bitor(self, rhs: F) -> Formula {
+        match self {
+            Formula::Or(mut formulas) => {
+                formulas.push(rhs.into());
+                Formula::Or(formulas)
+            }
+            formula => or([formula, rhs.into()]),
+        }
+    }
+}
+
+impl<F: Into<Formula>> BitXor<F> for Formula {
+    type Output = Formula;
+
+    fn bitxor(self, rhs: F) -> Formula {
+        xor(self, rhs)
+    }
+}
+
+impl Formula {
+    /// The linear constraints that force the formula to be true.
+    /// Binary variables are created for the sub-formulas that cannot be expressed
+    /// directly with the variables they contain.
+    ///
+    /// Returns an error if one of the variables is not binary.
+    pub fn constraints(
+        &self,
+        variables: &mut ProblemVariables,
+    ) -> Result<Vec<Constraint>, LinearizationError> {
+        self.check_binaries(variables)?;
+        let mut constraints = vec![];
+        self.enforce(variables, &mut constraints);
+        Ok(constraints)
+    }
+
+    /// An expression that is equal to 1 when the formula is true and to 0 otherwise,
+    /// with the constraints that define it.
+    ///
+    /// Returns an error if one of the variables is not binary.
+    pub fn to_expression(
+        &self,
+        variables: &mut ProblemVariables,
+    ) -> Result<(Expression, Vec<Constraint>), LinearizationError> {
+        self.check_binaries(variables)?;
+        let mut constraints = vec![];
+        let expression = self.truth_value(variables, &mut constraints);
+        Ok((expression, constraints))
+    }
+
+    fn check_binaries(&self, variables: &ProblemVariables) -> Result<(), LinearizationError> {
+        match self {
+            Formula::Var(var) => {
+                let def = variables.definition(*var);
+                if !def.is_integer || def.min < 0. || def.max > 1. {
+                    return Err(LinearizationError::NotBinary(*var));
+                }
+                Ok(())
+            }
+            Formula::Not(f) => f.check_binaries(variables),
+            Formula::Xor(a, b) | Formula::Implies(a, b) | Formula::Equivalent(a, b) => {
+                a.check_binaries(variables)?;
+                b.check_binaries(variables)
+            }
+            Formula::And(fs)
+            | Formula::Or(fs)
+            | Formula::AtLeast(_, fs)
+            | Formula::AtMost(_, fs)
+            | Formula::Exactly(_, fs) => fs.iter().try_for_each(|f| f.check_binaries(variables)),
+        }
+    }
+
+    /// Adds the constraints that make the formula true
+    fn enforce(&self, variables: &mut ProblemVariables, constraints: &mut Vec<Constraint>) {
+        match self {
+            Formula::And(fs) => {
+                for f in fs {
+                    f.enforce(variables, constraints);
+                }
+            }
+            Formula::Not(f) => match &**f {
+                Formula::Not(g) => g.enforce(variables, constraints),
+                Formula::And(fs) => or(fs.iter().cloned().map(not)).enforce(variables, constraints),
+                Formula::Or(fs) => {
+                    for g in fs {
+                        not(g.clone()).enforce(variables, constraints);
+                    }
+                }
+                Formula::Implies(a, b) => {
+                    a.enforce(variables, constraints);
+                    not((**b).clone()).enforce(variables, constraints);
+                }
+                Formula::Xor(a, b) => {
+                    equivalent((**a).clone(), (**b).clone()).enforce(variables, constraints)
+                }
+                Formula::Equivalent(a, b) => {
+                    xor((**a).clone(), (**b).clone()).enforce(variables, constraints)
+                }
+                Formula::AtLeast(k, fs) if *k > 0 => {
+                    Formula::AtMost(k - 1, fs.clone()).enforce(variables, constraints)
+                }
+                Formula::AtMost(k, fs) => {
+                    Formula::AtLeast(k + 1, fs.clone()).enforce(variables, constraints)
+                }
+                _ => {
+                    let value = f.truth_value(variables, constraints);
+                    constraints.push(constraint::eq(value, 0));
+                }
+            },
+            Formula::Or(fs) => {
+                let sum = sum_of_truth_values(fs, variables, constraints);
+                constraints.push(constraint::geq(sum, 1));
+            }
+            Formula::AtLeast(k, fs) => {
+                let sum = sum_of_truth_values(fs, variables, constraints);
+                constraints.push(constraint::geq(sum, *k as f64));
+            }
+            Formula::AtMost(k, fs) => {
+                let sum = sum_of_truth_values(fs, variables, constraints);
+                constraints.push(constraint::leq(sum, *k as f64));
+            }
+            Formula::Exactly(k, fs) => {
+                let sum = sum_of_truth_values(fs, variables, constraints);
+                constraints.push(constraint::eq(sum, *k as f64));
+            }
+            Formula::Xor(a, b) => {
+                let a = a.truth_value(variables, constraints);
+                let b = b.truth_value(variables, constraints);
+                constraints.push(constraint::eq(a + b, 1));
+            }
+            Formula::Implies(a, b) => {
+                let a = a.truth_value(variables, constraints);
+                let b = b.truth_value(variables, constraints);
+                constraints.push(constraint::leq(a, b));
+            }
+            Formula::Equivalent(a, b) => {
+                let a = a.truth_value(variables, constraints);
+                let b = b.truth_value(variables, constraints);
+                constraints.push(constraint::eq(a, b));
+            }
+            Formula::Var(var) => constraints.push(constraint::eq(*var, 1)),
+        }
+    }
+
+    /// An expression equal to the truth value of the formula.
+    /// Adds a binary variable and the constraints that define it when needed.
+    fn truth_value(
+        &self,
+        variables: &mut ProblemVariables,
+        constraints: &mut Vec<Constraint>,
+    ) -> Expression {
+        match self {
+            Formula::Var(var) => Expression::from(*var),
+            Formula::Not(f) => 1 - f.truth_value(variables, constraints),
+            Formula::And(fs) => {
+                let values = truth_values(fs, variables, constraints);
+                let result = variables.add(variable().binary());
+                let n = values.len() as f64;
+                for value in &values {
+                    constraints.push(constraint::leq(result, value.clone()));
+                }
+                let sum: Expression = values.into_iter().sum();
+                constraints.push(constraint::geq(result, sum - (n - 1.)));
+                result.into()
+            }
+            Formula::Or(fs) => {
+                let values = truth_values(fs, variables, constraints);
+                let result = variables.add(variable().binary());
+                for value in &values {
+                    constraints.push(constraint::geq(result, value.clone()));
+                }
+                let sum: Expression = values.into_iter().sum();
+                constraints.push(constraint::leq(result, sum));
+                result.into()
+            }
+            Formula::Xor(a, b) => {
+                let a = a.truth_value(variables, constraints);
+                let b = b.truth_value(variables, constraints);
+                let result = variables.add(variable().binary());
+                constraints.push(constraint::geq(result, a.clone() - b.clone()));
+                constraints.push(constraint::geq(result, b.clone() - a.clone()));
+                constraints.push(constraint::leq(result, a.clone() + b.clone()));
+                constraints.push(constraint::leq(result, 2 - a - b));
+                result.into()
+            }
+            Formula::Implies(a, b) => {
+                or([not((**a).clone()), (**b).clone()]).truth_value(variables, constraints)
+            }
+            Formula::Equivalent(a, b) => {
+                1 - xor((**a).clone(), (**b).clone()).truth_value(variables, constraints)
+            }
+            Formula::AtLeast(k, fs) => at_least_value(*k, fs, variables, constraints),
+            Formula::AtMost(k, fs) => 1 - at_least_value(k + 1, fs, variables, constraints),
+            Formula::Exactly(k, fs) => {
+                let at_least = Formula::AtLeast(*k, fs.clone());
+                let at_most = Formula::AtMost(*k, fs.clone());
+                and([at_least, at_most]).truth_value(variables, constraints)
+            }
+        }
+    }
+}
+
+fn truth_values(
+    formulas: &[Formula],
+    variables: &mut ProblemVariables,
+    constraints: &mut Vec<Constraint>,
+) -> Vec<Expression> {
+    formulas
+        .iter()
+        .map(|f| f.truth_value(variables, constraints))
+        .collect()
+}
+
+fn sum_of_truth_values(
+    formulas: &[Formula],
+    variables: &mut ProblemVariables,
+    constraints: &mut Vec<Constraint>,
+) -> Expression {
+    truth_values(formulas, variables, constraints)
+        .into_iter()
+        .sum()
+}
+
+/// A binary expression that is 1 when at least k formulas are true
+fn at_least_value(
+    k: usize,
+    formulas: &[Formula],
+    variables: &mut ProblemVariables,
+    constraints: &mut Vec<Constraint>,
+) -> Expression {
+    let n = formulas.len();
+    if k == 0 {
+        return Expression::from(1);
+    }
+    if k > n {
+        return Expression::from(0);
+    }
+    let sum = sum_of_truth_values(formulas, variables, constraints);
+    let result = variables.add(variable().binary());
+    // The sum is at least k when the result is 1, and at most k - 1 when it is 0
+    constraints.push(constraint::geq(sum.clone(), k as f64 * result));
+    constraints.push(constraint::leq(
+        sum,
+        (k - 1) as f64 + (n - k + 1) as f64 * result,
+    ));
+    result.into()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::linearization::LinearizationError;
+    use crate::variables;
+
+    use super::{at_most, exactly, implies, not, Formula};
+
+    #[test]
+    fn direct_constraints() {
+        variables! {vars: a (binary); b (binary); c (binary);}
+        let formula = Formula::from(a) | b | not(c);
+        let constraints = formula.constraints(&mut vars).unwrap();
+        assert_eq!(constraints.len(), 1);
+        assert_eq!(constraints[0].expression, c - a - b);
+        let constraints = implies(a, b).constraints(&mut vars).unwrap();
+        assert_eq!(constraints[0].expression, a - b);
+        // No new variable was needed
+        assert_eq!(vars.len(), 3);
+    }
+
+    #[test]
+    fn negations() {
+        variables! {vars: a (binary); b (binary);}
+        // not (a or b) is (not a) and (not b)
+        let constraints = not(Formula::from(a) | b).constraints(&mut vars).unwrap();
+        assert_eq!(constraints.len(), 2);
+        let constraints = not(exactly(1, [a, b])).constraints(&mut vars).unwrap();
+        assert!(constraints.len() > 1);
+        assert!(vars.len() > 2);
+    }
+
+    #[test]
+    fn not_binary() {
+        variables! {vars: a (binary); 0 <= b <= 2;}
+        assert_eq!(
+            at_most(1, [a, b]).constraints(&mut vars).unwrap_err(),
+            LinearizationError::NotBinary(b)
+        );
+    }
+}
@@ -0,0 +1,205 @@
+use crate::constraint::{self, ConstraintReference};
+use crate::solvers::{ObjectiveDirection, Solution, Solver, SolverModel};
+use crate::variable::UnsolvedProblem;
+use crate::{Constraint, Expression, IntoAffineExpression, ProblemVariables};
+
+/// How the objectives of a [MultiObjectiveProblem] are combined
+#[derive(Debug, Eq, PartialEq, Clone, Copy)]
+pub enum MultiObjectiveMode {
+    /// The objectives are optimised one after the other, from the highest priority to the lowest.
+    /// Once an objective is optimised, the following ones are not allowed to degrade its value
+    /// by more than its tolerance.
+    /// Objectives with the same priority are optimised in the order they were given.
+    Lexicographic,
+    /// A single problem is solved, with the weighted sum of the objectives as its objective.
+    /// The objectives that have to be maximised are subtracted, and the sum is minimised.
+    /// Tolerances are ignored.
+    Weighted,
+}
+
+/// An objective of a [MultiObjectiveProblem]
+#[derive(Debug, Clone)]
+struct Objective {
+    expression: Expression,
+    direction: ObjectiveDirection,
+    /// The priority in lexicographic mode, the weight in weighted mode
+    priority: f64,
+    tolerance: f64,
+}
+
+/// A problem with several objectives.
+/// Created with [ProblemVariables::optimise_multiple].
+///
+/// It is not tied to a solver: the solver is only given when [solving](MultiObjectiveProblem::solve),
+/// because the lexicographic mode creates a new model for each objective.
+#[derive(Clone)]
+pub struct MultiObjectiveProblem {
+    mode: MultiObjectiveMode,
+    variables: ProblemVariables,
+    objectives: Vec<Objective>,
+    constraints: Vec<Constraint>,
+}
+
+impl ProblemVariables {
+    /// Creates a problem with several objectives, given as
+    /// `(expression, direction, priority or weight, tolerance)`.
+    /// The third element is the priority of the objective in
+    /// [lexicographic](MultiObjectiveMode::Lexicographic) mode,
+    /// and its weight in [weighted](MultiObjectiveMode::Weighted) mode.
+    /// The tolerance is the absolute degradation of the optimal value of the objective that
+    /// is accepted to improve the objectives of lower priority.
+    ///
+    /// ```
+    /// # fn assert_float_eq(a:f64, b:f64) {
+    /// #   assert!((a-b).abs() <= 1e-6, "{} != {}", a, b);
+    /// # }
+    /// use good_lp::*;
+    /// use good_lp::solvers::ObjectiveDirection::*;
+    /// variables! {vars: 0 <= cheap <= 10; 0 <= fast <= 10;}
+    /// let objectives = [
+    ///     // The cost matters most, but can be 5 above its minimum
+    ///     (cheap + 3 * fast, Minimisation, 2., 5.),
+    ///     (fast.into(), Maximisation, 1., 0.),
+    /// ];
+    /// let solution = vars
+    ///     .optimise_multiple(MultiObjectiveMode::Lexicographic, objectives)
+    ///     .with(constraint!(cheap + fast == 10))
+    ///     .solve(default_solver)?;
+    /// assert_float_eq(solution.value(fast), 2.5);
+    /// # Ok::<_, ResolutionError>(())
+    /// ```
+    pub fn optimise_multiple<E: IntoAffineExpression, I>(
+        self,
+        mode: MultiObjectiveMode,
+        objectives: I,
+    ) -> MultiObjectiveProblem
+    where
+        I: IntoIterator<Item = (E, ObjectiveDirection, f64, f64)>,
+    {
+        let mut objectives: Vec<Objective> = objectives
+            .into_iter()
+            .map(|(expression, direction, priority, tolerance)| Objective {
+                expression: expression.into_expression(),
+                direction,
+                priority,
+                tolerance,
+            })
+            .collect();
+        assert!(!objectives.is_empty(), "a problem needs an objective");
+        if mode == MultiObjectiveMode::Lexicographic {
+            // The sort is stable: objectives with the same priority keep their order
+            objectives.sort_by(|a, b| b.priority.total_cmp(&a.priority));
+        }
+        MultiObjectiveProblem {
+            mode,
+            variables: self,
+            objectives,
+            constraints: vec![],
+        }
+    }
+}
+
+impl MultiObjectiveProblem {
+    /// Adds a constraint to the problem and returns a reference to it.
+    /// The reference is valid in the models created to solve the problem.
+    pub fn add_constraint(&mut self, constraint: Constraint) -> ConstraintReference {
+        let reference = ConstraintReference::new(self.constraints.len(), constraint.name.clone());
+        self.constraints.push(constraint);
+        reference
+    }
+
+    /// Takes a problem and adds a constraint to it
+    pub fn with(mut self, constraint: Constraint) -> Self {
+        self.add_constraint(constraint);
+        self
+    }
+
+    /// Solves the problem with the given solver, and returns the solution of the last
+    /// model that was solved. In lexicographic mode, there is one model per objective,
+    /// and the solution is the one of the objective with the lowest priority.
+    pub fn solve<S: Solver>(
+        self,
+        mut solver: S,
+    ) -> Result<<S::Model as SolverModel>::Solution, <S::Model as SolverModel>::Error> {
+        let MultiObjectiveProblem {
+            mode,
+            variables,
+            mut objectives,
+            mut constraints,
+        } = self;
+        if mode == MultiObjectiveMode::Weighted {
+            let objective: Expression = objectives
+                .into_iter()
+                .map(|o| match o.direction {
+                    ObjectiveDirection::Minimisation => o.priority * o.expression,
+                    ObjectiveDirection::Maximisation => -o.priority * o.expression,
+                })
+                .sum();
+            let problem = variables.minimise(objective);
+            return solve_with_constraints(&mut solver, problem, constraints);
+        }
+        let last = objectives.pop().expect("a problem needs an objective");
+        for objective in objectives {
+            let problem = variables
+                .clone()
+                .optimise(objective.direction, objective.expression.clone());
+            let solution = solve_with_constraints(&mut solver, problem, constraints.clone())?;
+            // The next objectives must not degrade this one by more than its tolerance
+            let value = solution.eval(objective.expression.clone());
+            constraints.push(match objective.direction {
+                ObjectiveDirection::Minimisation => {
+                    constraint::leq(objective.expression, value + objective.tolerance)
+                }
+                ObjectiveDirection::Maximisation => {
+                    constraint::geq(objective.expression, value - objective.tolerance)
+                }
+            });
+        }
+        let problem = variables.optimise(last.direction, last.expression);
+        solve_with_constraints(&mut solver, problem, constraints)
+    }
+}
+
+fn solve_with_constraints<S: Solver>(
+    solver: &mut S,
+    problem: UnsolvedProblem,
+    constraints: Vec<Constraint>,
+) -> Result<<S::Model as SolverModel>::Solution, <S::Model as SolverModel>::Error> {
+    let mut model = solver.create_model(problem);
+    for constraint in constraints {
+        model.add_constraint(constraint);
+    }
+    model.solve()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::solvers::ObjectiveDirection::*;
+    use crate::{variables, Expression, ProblemVariables};
+
+    use super::MultiObjectiveMode;
+
+    #[test]
+    fn priorities() {
+        variables! {vars: x; y; z;}
+        let objectives = [
+            (x, Minimisation, 1., 0.),
+            (y, Minimisation, 3., 0.),
+            (z, Maximisation, 1., 0.),
+        ];
+        let problem = vars.optimise_multiple(MultiObjectiveMode::Lexicographic, objectives);
+        let order: Vec<_> = problem
+            .objectives
+            .iter()
+            .map(|o| o.expression.clone())
+            .collect();
+        assert_eq!(order, vec![y.into(), x.into(), z.into()]);
+    }
+
+    #[test]
+    #[should_panic(expected = "objective")]
+    fn no_objective() {
+        let objectives: Vec<(Expression, _, _, _)> = vec![];
+        ProblemVariables::new().optimise_multiple(MultiObjectiveMode::Weighted, objectives);
+    }
+}
@@ -0,0 +1,141 @@
+use crate::solvers::{ModelWithSOS2, SolverModel};
+use crate::variable::{variable, ProblemVariables};
+use crate::{constraint, Constraint, Expression, IntoAffineExpression, Variable};
+
+/// A piecewise-linear function `y = f(x)`, defined by its breakpoints.
+/// Between two breakpoints, the function is linear.
+/// Outside of the first and the last breakpoint, it is not defined,
+/// so `x` is constrained to stay between them.
+///
+/// The function is modelled with one weight variable per breakpoint:
+/// `x` and `y` are the weighted sums of the coordinates of the breakpoints,
+/// and at most two consecutive weights can be non-zero.
+/// This last condition is enforced either with an
+/// [SOS2 constraint](PiecewiseLinear::add_sos2_constraints),
+/// or with [binary variables](PiecewiseLinear::binary_constraints).
+///
+/// Created with [ProblemVariables::add_piecewise_linear].
+#[derive(Debug, Clone)]
+pub struct PiecewiseLinear {
+    x: Expression,
+    breakpoints: Vec<(f64, f64)>,
+    weights: Vec<Variable>,
+}
+
+impl ProblemVariables {
+    /// Creates a [piecewise-linear function](PiecewiseLinear) of `x`,
+    /// going through the given `(x, y)` breakpoints.
+    ///
+    /// # Panics
+    ///
+    /// If there are less than two breakpoints,
+    /// or if their x coordinates are not strictly increasing.
+    ///
+    /// ```
+    /// use good_lp::*;
+    /// variables! {vars: 0 <= power <= 100;}
+    /// // The price of electricity increases with the consumed power
+    /// let tariff = vars.add_piecewise_linear(power, &[(0., 0.), (50., 100.), (100., 400.)]);
+    /// let constraints = tariff.binary_constraints(&mut vars);
+    /// let mut model = vars
+    ///     .maximise(4 * power - tariff.value())
+    ///     .using(default_solver);
+    /// for constraint in constraints {
+    ///     model.add_constraint(constraint);
+    /// }
+    /// let solution = model.solve()?;
+    /// // Above 50, each unit costs more than it brings
+    /// assert!((solution.value(power) - 50.).abs() < 1e-6);
+    /// # Ok::<_, ResolutionError>(())
+    /// ```
+    pub fn add_piecewise_linear<E: IntoAffineExpression>(
+        &mut self,
+        x: E,
+        breakpoints: &[(f64, f64)],
+    ) -> PiecewiseLinear {
+        assert!(
+            breakpoints.len() >= 2,
+            "a piecewise-linear function needs at least two breakpoints"
+        );
+        assert!(
+            breakpoints.windows(2).all(|w| w[0].0 < w[1].0),
+            "the breakpoints of a piecewise-linear function must be sorted by x"
+        );
+        let weights = self.add_vector(variable().clamp(0, 1), breakpoints.len());
+        PiecewiseLinear {
+            x: x.into_expression(),
+            breakpoints: breakpoints.to_vec(),
+            weights,
+        }
+    }
+}
+
+impl PiecewiseLinear {
+    /// The value of the function, `y`
+    pub fn value(&self) -> Expression {
+        self.weights
+            .iter()
+            .zip(&self.breakpoints)
+            .map(|(&weight, &(_, y))| y * weight)
+            .sum()
+    }
+
+    /// The weight variables of the breakpoints
+    pub fn weights(&self) -> &[Variable] {
+        &self.weights
+    }
+
+    /// The constraints that link `x` and `y` to the weights,
+    /// without the condition on the number of non-zero weights
+    fn linking_constraints(&self) -> Vec<Constraint> {
+        let x: Expression = self
+            .weights
+            .iter()
+            .zip(&self.breakpoints)
+            .map(|(&weight, &(x, _))| x * weight)
+            .sum();
+        let total: Expression = self.weights.iter().sum();
+        vec![constraint::eq(total, 1), constraint::eq(self.x.clone(), x)]
+    }
+
+    /// Adds the function to a model that supports [SOS2 constraints](ModelWithSOS2).
+    pub fn add_sos2_constraints<M: SolverModel + ModelWithSOS2>(&self, model: &mut M) {
+        for constraint in self.linking_constraints() {
+            model.add_constraint(constraint);
+        }
+        let ordered: Expression = self
+            .weights
+            .iter()
+            .enumerate()
+            .map(|(i, &weight)| (i + 1) as f64 * weight)
+            .sum();
+        model.add_sos2(ordered);
+    }
+
+    /// Creates one binary variable per segment of the function, and returns the constraints
+    /// that define the function with them. Works with all the solvers that support
+    /// integer variables.
+    pub fn binary_constraints(&self, variables: &mut ProblemVariables) -> Vec<Constraint> {
+        let segments = variables.add_vector(variable().binary(), self.weights.len() - 1);
+        let mut constraints = self.linking_constraints();
+        constraints.push(constraint::eq(segments.iter().sum::<Expression>(), 1));
+        for (i, &weight) in self.weights.iter().enumerate() {
+            // A weight can only be non-zero if one of the segments around it is selected
+            let around = &segments[i.saturating_sub(1)..(i + 1).min(segments.len())];
+            constraints.push(constraint::leq(weight, around.iter().sum::<Expression>()));
+        }
+        constraints
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::variables;
+
+    #[test]
+    #[should_panic(expected = "sorted")]
+    fn unsorted_breakpoints() {
+        variables! {vars: x;}
+        vars.add_piecewise_linear(x, &[(0., 0.), (2., 1.), (1., 3.)]);
+    }
+}
@@ -0,0 +1,351 @@
+//! Quadratic expressions, such as `x * y + 2 * x * x - 3 * z + 1`.
+//! They can be used as objective functions with the solvers that support them.
+use std::ops::{Add, AddAssign, Div, Mul, MulAssign, Neg, Sub, SubAssign};
+
+use fnv::FnvHashMap as HashMap;
+
+use crate::affine_expression_trait::IntoAffineExpression;
+use crate::{Expression, Solution, Variable};
+
+/// The products of two variables in a [QuadraticExpression], with their coefficients
+#[derive(Debug, Clone, PartialEq, Default)]
+pub(crate) struct QuadraticTerms {
+    /// The variable with the lowest index comes first in each pair
+    pub(crate) coefficients: HashMap<(Variable, Variable), f64>,
+}
+
+impl QuadraticTerms {
+    fn add(&mut self, a: Variable, b: Variable, coefficient: f64) {
+        let key = if a.index() <= b.index() {
+            (a, b)
+        } else {
+            (b, a)
+        };
+        *self.coefficients.entry(key).or_default() += coefficient;
+    }
+
+    pub(crate) fn is_empty(&self) -> bool {
+        self.coefficients.values().all(|&c| c == 0.)
+    }
+
+    /// The products and their coefficients, sorted by variable
+    pub(crate) fn sorted(&self) -> Vec<(Variable, Variable, f64)> {
+        let mut terms: Vec<_> = self
+            .coefficients
+            .iter()
+            .filter(|(_, &c)| c != 0.)
+            .map(|(&(a, b), &c)| (a, b, c))
+            .collect();
+        terms.sort_unstable_by_key(|&(a, b, _)| (a.index(), b.index()));
+        terms
+    }
+}
+
+/// A quadratic expression: a sum of products of two variables, plus an [affine expression](Expression).
+///
+/// Multiplying two variables or two expressions gives a quadratic expression,
+/// that can be used as the objective of a problem with the solvers that support it:
+/// SCIP, and HiGHS for problems without integer variables.
+/// The other solvers return an error when solving such a problem.
+///
+/// ```
+/// use good_lp::*;
+/// variables! {vars: x; y;}
+/// // Least squares: the point of x + y = 4 that is the closest to (1, 2)
+/// let objective = (x - 1) * (x - 1) + (y - 2) * (y - 2);
+/// let values: std::collections::HashMap<_, _> = vec![(x, 1.5), (y, 2.5)].into_iter().collect();
+/// assert_eq!(objective.eval_with(&values), 0.5);
+/// # #[cfg(feature = "scip")] {
+/// let solution = vars
+///     .minimise(objective)
+///     .using(scip)
+///     .with(constraint!(x + y == 4))
+///     .solve()?;
+/// assert!((solution.value(x) - 1.5).abs() < 1e-6);
+/// # }
+/// # Ok::<_, ResolutionError>(())
+/// ```
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct QuadraticExpression {
+    pub(crate) quadratic: QuadraticTerms,
+    pub(crate) affine: Expression,
+}
+
+impl QuadraticExpression {
+    /// Create a quadratic expression without any quadratic term
+    pub fn from_affine<E: IntoAffineExpression>(expression: E) -> Self {
+        QuadraticExpression {
+            quadratic: QuadraticTerms::default(),
+            affine: expression.into_expression(),
+        }
+    }
+
+    /// Adds `coefficient * a * b` to the expression
+    pub fn add_product(&mut self, coefficient: f64, a: Variable, b: Variable) {
+        self.quadratic.add(a, b, coefficient);
+    }
+
+    /// The products of two variables in the expression, with their coefficients.
+    /// Each pair of variables appears once, with the variable created first in the first position.
+    /// The pairs are sorted in the order the variables were created.
+    pub fn quadratic_terms(&self) -> impl Iterator<Item = (Variable, Variable, f64)> {
+        self.quadratic.sorted().into_iter()
+    }
+
+    /// The affine part of the expression
+    pub fn affine_part(&self) -> &Expression {
+        &self.affine
+    }
+
+    /// Whether the expression does not contain any product of variables
+    pub fn is_affine(&self) -> bool {
+        self.quadratic.is_empty()
+    }
+
+    /// Evaluate the value of the expression, given the values of the variables
+    pub fn eval_with<S: Solution>(&self, values: &S) -> f64 {
+        let quadratic: f64 = self
+            .quadratic_terms()
+            .map(|(a, b, coefficient)| coefficient * values.value(a) * values.value(b))
+            .sum();
+        quadratic + IntoAffineExpression::eval_with(&self.affine, values)
+    }
+}
+
+/// The product of two affine expressions
+fn product(lhs: Expression, rhs: Expression) -> QuadraticExpression {
+    let mut affine = Expression::from_other_affine(&rhs);
+    affine *= lhs.constant;
+    affine.add_mul(rhs.constant, &lhs.linear);
+    let mut result = QuadraticExpression::from_affine(affine);
+    for (&a, &coefficient_a) in &lhs.linear.coefficients {
+        for (&b, &coefficient_b) in &rhs.linear.coefficients {
+            result.add_product(coefficient_a * coefficient_b, a, b);
+        }
+    }
+    result
+}
+
+macro_rules! impl_product {
+    ($($lhs:ty : $rhs:ty),*) => {$(
+        impl Mul<$rhs> for $lhs {
+            type Output = QuadraticExpression;
+
+            fn mul(self, rhs: $rhs) -> QuadraticExpression {
+                product(Expression::from(self), Expression::from(rhs))
+            }
+        }
+    )*}
+}
+impl_product!(Variable: Variable, Variable: Expression, Expression: Variable, Expression: Expression);
+
+impl<E: IntoAffineExpression> From<E> for QuadraticExpression {
+    fn from(expression: E) -> Self {
+        QuadraticExpression::from_affine(expression)
+    }
+}
+
+impl<RHS: IntoAffineExpression> AddAssign<RHS> for QuadraticExpression {
+    fn add_assign(&mut self, rhs: RHS) {
+        self.affine += rhs;
+    }
+}
+
+impl<RHS: IntoAffineExpression> SubAssign<RHS> for QuadraticExpression {
+    fn sub_assign(&mut self, rhs: RHS) {
+        self.affine -= rhs;
+    }
+}
+
+impl AddAssign<QuadraticExpression> for QuadraticExpression {
+    fn add_assign(&mut self, rhs: QuadraticExpression) {
+        for (a, b, coefficient) in rhs.quadratic_terms() {
+            self.add_product(coefficient, a, b);
+        }
+        self.affine += rhs.affine;
+    }
+}
+
+impl SubAssign<QuadraticExpression> for QuadraticExpression {
+    fn sub_assign(&mut self, rhs: QuadraticExpression) {
+        *self += -rhs;
+    }
+}
+
+impl<N: Into<f64>> MulAssign<N> for QuadraticExpression {
+    fn mul_assign(&mut self, rhs: N) {
+        let factor = rhs.into();
+        for coefficient in self.quadratic.coefficients.values_mut() {
+            *coefficient *= factor;
+        }
+        self.affine *= factor;
+    }
+}
+
+impl<N: Into<f64>> Mul<N> for QuadraticExpression {
+    type Output = QuadraticExpression;
+
+    fn mul(mut self, rhs: N) -> Self::Output {
+        self *= rhs;
+        self
+    }
+}
+
+impl<N: Into<f64>> Div<N> for QuadraticExpression {
+    type Output = QuadraticExpression;
+
+    fn div(mut self, rhs: N) -> Self::Output {
+        self *= 1. / rhs.into();
+        self
+    }
+}
+
+impl Neg for QuadraticExpression {
+    type Output = QuadraticExpression;
+
+    fn neg(mut self) -> Self::Output {
+        self *= -1;
+        self
+    }
+}
+
+macro_rules! impl_scalar_mul {
+    ($($t:ty),*) =>{$(
+        impl Mul<QuadraticExpression> for $t {
+            type Output = QuadraticExpression;
+
+            fn mul(self, mut rhs: QuadraticExpression) -> Self::Output {
+                rhs *= self;
+                rhs
+            }
+        }
+    )*}
+}
+impl_scalar_mul!(f64, i32);
+
+impl<RHS: IntoAffineExpression> Add<RHS> for QuadraticExpression {
+    type Output = QuadraticExpression;
+
+    fn add(mut self, rhs: RHS) -> Self::Output {
+        self += rhs;
+        self
+    }
+}
+
+impl<RHS: IntoAffineExpression> Sub<RHS> for QuadraticExpression {
+    type Output = QuadraticExpression;
+
+    fn sub(mut self, rhs: RHS) -> Self::Output {
+        self -= rhs;
+        self
+    }
+}
+
+macro_rules! impl_quadratic_ops {
+    ($($t:ty),*) =>{$(
+        impl Add<QuadraticExpression> for $t {
+            type Output = QuadraticExpression;
+
+            fn add(self, mut rhs: QuadraticExpression) -> Self::Output {
+                rhs += self;
+                rhs
+            }
+        }
+
+        impl Sub<QuadraticExpression> for $t {
+            type Output = QuadraticExpression;
+
+            fn sub(self, rhs: QuadraticExpression) -> Self::Output {
+                -rhs + self
+            }
+        }
+    )*}
+}
+impl_quadratic_ops!(QuadraticExpression, Expression, Variable, f64, i32);
+
+impl<E: Into<QuadraticExpression>> std::iter::Sum<E> for QuadraticExpression {
+    fn sum<I: Iterator<Item = E>>(iter: I) -> Self {
+        let mut result = QuadraticExpression::default();
+        for expression in iter {
+            let expression: QuadraticExpression = expression.into();
+            result += expression;
+        }
+        result
+    }
+}
+
+/// An objective function: an affine expression, or a [QuadraticExpression].
+/// Accepted by [ProblemVariables::optimise](crate::ProblemVariables::optimise).
+pub trait IntoObjective {
+    /// Transform the value into a quadratic expression, which may not have any quadratic term
+    fn into_quadratic_expression(self) -> QuadraticExpression;
+}
+
+impl<E: IntoAffineExpression> IntoObjective for E {
+    fn into_quadratic_expression(self) -> QuadraticExpression {
+        QuadraticExpression::from_affine(self)
+    }
+}
+
+impl IntoObjective for QuadraticExpression {
+    fn into_quadratic_expression(self) -> QuadraticExpression {
+        self
+    }
+}
+
+#[cfg(feature = "serde")]
+mod serialization {
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    use super::QuadraticTerms;
+    use crate::variable::Variable;
+
+    impl Serialize for QuadraticTerms {
+        fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            self.sorted().serialize(serializer)
+        }
+    }
+
+    impl<'de> Deserialize<'de> for QuadraticTerms {
+        fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+            let mut terms = QuadraticTerms::default();
+            for (a, b, coefficient) in Vec::<(Variable, Variable, f64)>::deserialize(deserializer)?
+            {
+                terms.add(a, b, coefficient);
+            }
+            Ok(terms)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use crate::{variables, Expression};
+
+    use super::QuadraticExpression;
+
+    #[test]
+    fn products() {
+        variables! {vars: x; y;}
+        let expr = (x + 1) * (2 * y - 3);
+        let terms: Vec<_> = expr.quadratic_terms().collect();
+        assert_eq!(terms, [(x, y, 2.)]);
+        assert_eq!(*expr.affine_part(), -3 * x + 2 * y - 3);
+        // The order of the variables does not matter
+        assert_eq!(x * y + y * x, 2 * x * y);
+        assert!(!(x * x).is_affine());
+        assert!((x * x - x * x).is_affine());
+    }
+
+    #[test]
+    fn eval() {
+        variables! {vars: x; y;}
+        let values: HashMap<_, _> = vec![(x, 3.), (y, -2.)].into_iter().collect();
+        let squares: QuadraticExpression = [x, y].iter().map(|&v| v * v).sum();
+        assert_eq!(squares.eval_with(&values), 13.);
+        let expr = 2 * (x * y) - (x - y) + Expression::from(5) / 2;
+        assert_eq!(expr.eval_with(&values), -12. - 5. + 2.5);
+    }
+}
@@ -0,0 +1,88 @@
+use crate::constraint::ConstraintReference;
+use crate::Variable;
+
+/// How much a coefficient of a problem can change in each direction
+/// before the solution stops being optimal. See [SensitivityReport].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SensitivityRange {
+    /// How much the coefficient can decrease. It can be infinite.
+    pub allowable_decrease: f64,
+    /// How much the coefficient can increase. It can be infinite.
+    pub allowable_increase: f64,
+}
+
+/// A sensitivity (or ranging) analysis of the solution of a linear problem.
+///
+/// For each variable, it contains the range over which its objective coefficient can vary
+/// while the solution stays optimal. For each constraint, it contains the range over which
+/// its bound can vary while its [dual value](crate::solvers::DualValues) stays the same.
+/// The bound is the one of the linear part of the constraint's expression, as in
+/// [ModifiableModel::set_constraint_bounds](crate::ModifiableModel::set_constraint_bounds).
+/// For a constraint with both a lower and an upper bound, it is the one the solution is equal to,
+/// or the upper one if it is equal to none.
+///
+/// Returned by [SolutionWithSensitivity::sensitivity_report].
+#[derive(Debug, Clone, PartialEq)]
+pub struct SensitivityReport {
+    objective: Vec<SensitivityRange>,
+    constraints: Vec<SensitivityRange>,
+}
+
+impl SensitivityReport {
+    /// Creates a report from the ranges of the objective coefficients of all the variables,
+    /// and of the bounds of all the constraints, in the order they were added
+    pub fn new(objective: Vec<SensitivityRange>, constraints: Vec<SensitivityRange>) -> Self {
+        SensitivityReport {
+            objective,
+            constraints,
+        }
+    }
+
+    /// The range of the objective coefficient of a variable
+    pub fn objective_coefficient(&self, variable: Variable) -> SensitivityRange {
+        self.objective[variable.index()]
+    }
+
+    /// The range of the bound of a constraint
+    pub fn constraint_bound(&self, constraint: &ConstraintReference) -> SensitivityRange {
+        self.constraints[constraint.index]
+    }
+}
+
+/// A solution on which a [SensitivityReport] can be computed.
+///
+/// It is implemented for HiGHS, lp_solve and minilp.
+/// HiGHS and lp_solve compute the ranges over which their optimal basis stays optimal.
+/// HiGHS only does it when it is enabled with `HighsProblem::set_ranging`.
+/// minilp does not expose its basis, so the ranges are found by solving
+/// two linear problems for every variable and every constraint:
+/// they are the ones over which the solution and the dual values stay optimal,
+/// which can be larger than the ranges of the basis when the solution is degenerate.
+/// It is not implemented for cbc and SCIP, whose bindings do not expose ranging information.
+///
+/// ```
+/// use good_lp::*;
+/// # #[cfg(feature = "minilp")] {
+/// variables! {vars: 0 <= x; 0 <= y;}
+/// let mut model = vars.maximise(3 * x + 2 * y).using(minilp);
+/// let c = model.add_constraint(constraint!(x + y <= 4));
+/// model.add_constraint(constraint!(x + 3 * y <= 9));
+/// model.add_constraint(constraint!(x <= 3));
+/// let solution = model.solve()?;
+/// assert_eq!((solution.value(x), solution.value(y)), (3., 1.));
+///
+/// let report = solution.sensitivity_report();
+/// // x stays at 3 as long as its coefficient is at least the one of y
+/// assert_eq!(report.objective_coefficient(x).allowable_decrease, 1.);
+/// assert_eq!(report.objective_coefficient(x).allowable_increase, f64::INFINITY);
+/// // The shadow price of the constraint is valid while its bound is between 3 and 5
+/// assert_eq!(report.constraint_bound(&c).allowable_decrease, 1.);
+/// assert_eq!(report.constraint_bound(&c).allowable_increase, 1.);
+/// # }
+/// # Ok::<_, ResolutionError>(())
+/// ```
+pub trait SolutionWithSensitivity {
+    /// Computes the sensitivity report of the solution.
+    /// Integer variables are considered fixed to their value in the solution.
+    fn sensitivity_report(&self) -> SensitivityReport;
+}
@@ -0,0 +1,150 @@
+use crate::constraint::ConstraintReference;
+use crate::solvers::{ObjectiveDirection, Solution};
+use crate::variable::{variable, ProblemDescription};
+use crate::{Constraint, IntoAffineExpression, Variable};
+
+/// A constraint that can be violated, at the price of a penalty in the objective.
+/// Created with [Constraint::soft].
+///
+/// The constraint is relaxed with non-negative slack variables, and the penalty times the
+/// amount of the violation is added to the objective: it is added when minimising,
+/// and subtracted when maximising.
+#[derive(Debug, Clone)]
+pub struct SoftConstraint {
+    constraint: Constraint,
+    penalty: f64,
+}
+
+impl Constraint {
+    /// Creates a [soft constraint](SoftConstraint), that can be violated with the given
+    /// penalty per unit of violation.
+    ///
+    /// # Panics
+    ///
+    /// If the penalty is negative or not finite
+    ///
+    /// ```
+    /// use good_lp::*;
+    /// variables! {vars: 0 <= x; 0 <= y;}
+    /// let mut problem = ProblemDescription::new(vars.minimise(x + y));
+    /// problem.add_constraint(constraint!(x + y <= 10));
+    /// // These goals cannot be reached together
+    /// let x_goal = problem.add_soft_constraint(constraint!(x >= 8).soft(3.));
+    /// let y_goal = problem.add_soft_constraint(constraint!(y >= 6).soft(2.));
+    /// let solution = problem.using(default_solver).solve()?;
+    /// assert!(x_goal.violation(&solution).abs() < 1e-6);
+    /// assert!((y_goal.violation(&solution) - 4.).abs() < 1e-6);
+    /// # Ok::<_, ResolutionError>(())
+    /// ```
+    pub fn soft(self, penalty: f64) -> SoftConstraint {
+        assert!(
+            penalty.is_finite() && penalty >= 0.,
+            "the penalty of a soft constraint must be finite and non-negative"
+        );
+        SoftConstraint {
+            constraint: self,
+            penalty,
+        }
+    }
+}
+
+impl SoftConstraint {
+    /// The constraint that should hold
+    pub fn constraint(&self) -> &Constraint {
+        &self.constraint
+    }
+
+    /// The penalty per unit of violation of the constraint
+    pub fn penalty(&self) -> f64 {
+        self.penalty
+    }
+}
+
+/// A reference to a [SoftConstraint] that was added to a problem,
+/// used to get its violation in a solution
+#[derive(Debug, Clone, PartialEq)]
+pub struct SoftConstraintReference {
+    reference: ConstraintReference,
+    /// The amount by which the upper bound of the constraint is exceeded
+    excess: Variable,
+    /// The amount by which the value is below the lower bound, if there is one
+    shortfall: Option<Variable>,
+}
+
+impl SoftConstraintReference {
+    /// The reference of the relaxed constraint in the problem
+    pub fn reference(&self) -> &ConstraintReference {
+        &self.reference
+    }
+
+    /// The slack variables that measure the violation of the constraint
+    pub fn slack_variables(&self) -> impl Iterator<Item = Variable> {
+        std::iter::once(self.excess).chain(self.shortfall)
+    }
+
+    /// By how much the constraint is violated in the solution, or 0 if it holds
+    pub fn violation<S: Solution>(&self, solution: &S) -> f64 {
+        self.slack_variables().map(|var| solution.value(var)).sum()
+    }
+}
+
+impl ProblemDescription {
+    /// Adds a [soft constraint](SoftConstraint) to the problem: creates its slack variables,
+    /// adds the relaxed constraint, and adds the penalty of the violation to the objective.
+    pub fn add_soft_constraint(&mut self, soft: SoftConstraint) -> SoftConstraintReference {
+        let SoftConstraint {
+            mut constraint,
+            penalty,
+        } = soft;
+        let mut add_slack = |suffix: &str| {
+            let mut definition = variable().min(0);
+            if let Some(name) = &constraint.name {
+                definition = definition.name(format!("{}_{}", name, suffix));
+            }
+            self.problem.variables.add(definition)
+        };
+        let excess = add_slack("excess");
+        let has_lower_bound = constraint.is_equality || constraint.lower > f64::NEG_INFINITY;
+        let shortfall = if has_lower_bound {
+            Some(add_slack("shortfall"))
+        } else {
+            None
+        };
+        let mut violation = excess.into_expression();
+        constraint.expression -= excess;
+        if let Some(shortfall) = shortfall {
+            violation += shortfall;
+            constraint.expression += shortfall;
+        }
+        match self.problem.direction {
+            ObjectiveDirection::Minimisation => self.problem.objective += penalty * violation,
+            ObjectiveDirection::Maximisation => self.problem.objective -= penalty * violation,
+        }
+        SoftConstraintReference {
+            reference: self.add_constraint(constraint),
+            excess,
+            shortfall,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{constraint, variables, ProblemDescription};
+
+    #[test]
+    fn slack_variables() {
+        variables! {vars: 0 <= x <= 10;}
+        let mut problem = ProblemDescription::new(vars.maximise(x));
+        let upper = problem.add_soft_constraint(constraint!(x <= 4).soft(2.));
+        assert_eq!(upper.slack_variables().count(), 1);
+        let equal = problem.add_soft_constraint(constraint!(x == 4).named("four").soft(1.));
+        assert_eq!(equal.slack_variables().count(), 2);
+        assert_eq!(equal.reference().name(), Some("four"));
+        assert_eq!(problem.variables().len(), 4);
+        let (excess, shortfall) = (upper.excess, equal.shortfall.unwrap());
+        assert_eq!(problem.constraints()[0].expression, x - excess - 4);
+        assert_eq!(problem.objective().linear.coefficients.len(), 4);
+        assert_eq!(problem.objective().linear.coefficients[&shortfall], -1.);
+    }
+}
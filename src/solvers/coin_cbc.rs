@@ -1,28 +1,40 @@
 //! A solver that uses a [Cbc](https://www.coin-or.org/Cbc/) [native library binding](https://docs.rs/coin_cbc).
 //! This solver is activated using the default `coin_cbc` feature.
 //! You can disable it an enable another solver instead using cargo features.
-use std::convert::TryInto;
 
-use coin_cbc::{raw::Status, Col, Model, Sense, Solution as CbcSolution};
+use coin_cbc::{
+    raw::{SecondaryStatus, Status},
+    Col, Model, Row, Sense, Solution as CbcSolution,
+};
 
-use crate::solvers::{MipGapError, ModelWithSOS1, WithMipGap};
+use crate::cardinality_constraint_solver_trait::add_cardinality_with_indicators;
+use crate::solvers::{
+    c_int_seed, MipGapError, ModelWithSOS1, ModifiableModel, SolutionInfo, SolutionStatus,
+    WithInitialSolution, WithMipGap, WithRandomSeed, WithThreads, WithTimeLimit, WithVerbosity,
+    UNSUPPORTED_QUADRATIC_OBJECTIVE,
+};
 use crate::variable::{UnsolvedProblem, VariableDefinition};
 use crate::{
     constraint::ConstraintReference,
     solvers::{ObjectiveDirection, ResolutionError, Solution, SolverModel},
     IntoAffineExpression,
 };
-use crate::{Constraint, Variable};
+use crate::{CardinalityConstraintSolver, Constraint, Variable};
 
 /// The Cbc [COIN-OR](https://www.coin-or.org/) solver library.
 /// To be passed to [`UnsolvedProblem::using`](crate::variable::UnsolvedProblem::using)
 pub fn coin_cbc(to_solve: UnsolvedProblem) -> CoinCbcProblem {
     let UnsolvedProblem {
         objective,
+        quadratic_objective,
         direction,
         variables,
     } = to_solve;
     let mut model = Model::default();
+    let column_bounds = variables
+        .iter_variables_with_def()
+        .map(|(_, def)| (def.min, def.max))
+        .collect();
     let columns: Vec<Col> = variables
         .into_iter()
         .map(
@@ -55,8 +67,16 @@ pub fn coin_cbc(to_solve: UnsolvedProblem) -> CoinCbcProblem {
     CoinCbcProblem {
         model,
         columns,
+        column_bounds,
+        rows: vec![],
         has_sos: false,
         mip_gap: None,
+        time_limit: None,
+        threads: None,
+        quiet: false,
+        random_seed: None,
+        objective_constant: objective.constant,
+        has_quadratic_objective: !quadratic_objective.is_empty(),
     }
 }
 
@@ -65,8 +85,20 @@ pub fn coin_cbc(to_solve: UnsolvedProblem) -> CoinCbcProblem {
 pub struct CoinCbcProblem {
     model: Model,
     columns: Vec<Col>,
+    // cbc does not give access to the bounds of the columns
+    column_bounds: Vec<(f64, f64)>,
+    // the rows of the constraints, without the row added for SOS constraints
+    rows: Vec<Row>,
     has_sos: bool,
     mip_gap: Option<f32>,
+    time_limit: Option<f64>,
+    threads: Option<u32>,
+    // cbc displays its logs by default
+    quiet: bool,
+    random_seed: Option<u32>,
+    objective_constant: f64,
+    // Solving fails if the objective is quadratic
+    has_quadratic_objective: bool,
 }
 
 impl CoinCbcProblem {
@@ -96,15 +128,17 @@ impl CoinCbcProblem {
     }
 }
 
-impl SolverModel for CoinCbcProblem {
-    type Solution = CoinCbcSolution;
-    type Error = ResolutionError;
-
-    fn solve(mut self) -> Result<Self::Solution, Self::Error> {
+impl CoinCbcProblem {
+    fn solve_model(&mut self) -> Result<CoinCbcSolution, ResolutionError> {
+        if self.has_quadratic_objective {
+            return Err(UNSUPPORTED_QUADRATIC_OBJECTIVE);
+        }
         // Due to a bug in cbc, SOS constraints are only taken into account
         // if the model has at least one integer variable.
         // See: https://github.com/coin-or/Cbc/issues/376
         if self.has_sos {
+            // The workaround only needs to be applied once to a model that is solved many times
+            self.has_sos = false;
             // We need to add two columns to work around yet another bug
             // See: https://github.com/coin-or/Cbc/issues/376#issuecomment-803057782
             let dummy_col1 = self.model.add_col();
@@ -116,49 +150,105 @@ impl SolverModel for CoinCbcProblem {
             self.model.set_weight(dummy_row, dummy_col1, 1.);
             self.model.set_weight(dummy_row, dummy_col2, 1.);
             self.model.set_row_upper(dummy_row, 1.);
+            // Variables added later must keep the same index as their cbc column
+            self.columns.extend([dummy_col1, dummy_col2]);
+            self.column_bounds.extend([(0., f64::INFINITY); 2]);
         }
 
         if let Some(mip_gap) = self.mip_gap {
             self.set_parameter("ratiogap", &mip_gap.to_string());
         }
 
+        if let Some(time_limit) = self.time_limit {
+            self.set_parameter("sec", &time_limit.to_string());
+        }
+
+        if let Some(threads) = self.threads {
+            self.set_parameter("threads", &threads.to_string());
+        }
+
+        if self.quiet {
+            self.model.set_log_level(0);
+        }
+
+        if let Some(seed) = self.random_seed {
+            self.set_parameter("randomCbcSeed", &c_int_seed(seed).to_string());
+        }
+
         let solution = self.model.solve();
         let raw = solution.raw();
         match raw.status() {
-            Status::Stopped => Err(ResolutionError::Other("Stopped")),
             Status::Abandoned => Err(ResolutionError::Other("Abandoned")),
             Status::UserEvent => Err(ResolutionError::Other("UserEvent")),
-            Status::Finished // The optimization finished, but may not have found a solution
+            Status::Stopped // A limit was reached, there may be a solution
+            | Status::Finished // The optimization finished, but may not have found a solution
             | Status::Unlaunched // The solver didn't have to be launched, presolve handled it
             => {
                 if raw.is_continuous_unbounded() {
                     Err(ResolutionError::Unbounded)
                 } else if raw.is_proven_infeasible() {
                     Err(ResolutionError::Infeasible)
+                } else if !has_incumbent(raw) {
+                    Err(ResolutionError::Other(match raw.status() {
+                        Status::Stopped => "Stopped before finding a solution",
+                        _ => "No solution found",
+                    }))
                 } else {
+                    let status = match raw.secondary_status() {
+                        SecondaryStatus::StoppedOnGap => SolutionStatus::GapLimit,
+                        SecondaryStatus::StoppedOnTime => SolutionStatus::TimeLimit,
+                        SecondaryStatus::StoppedOnNodes
+                        | SecondaryStatus::StoppedOnSolutions
+                        | SecondaryStatus::StoppedOnIterationLimit
+                        | SecondaryStatus::StoppedOnUserEvent => SolutionStatus::Feasible,
+                        _ if raw.is_proven_optimal() => SolutionStatus::Optimal,
+                        _ => SolutionStatus::Feasible,
+                    };
                     let solution_vec = solution.raw().col_solution().into();
                     Ok(CoinCbcSolution {
                         solution,
                         solution_vec,
+                        status,
+                        objective_constant: self.objective_constant,
                     })
                 }
             },
         }
     }
 
+    fn add_binary(&mut self) -> Variable {
+        let col = self.model.add_binary();
+        self.columns.push(col);
+        self.column_bounds.push((0., 1.));
+        Variable::at(self.model.num_cols() as usize - 1)
+    }
+}
+
+impl SolverModel for CoinCbcProblem {
+    type Solution = CoinCbcSolution;
+    type Error = ResolutionError;
+
+    fn solve(mut self) -> Result<Self::Solution, Self::Error> {
+        self.solve_model()
+    }
+
     fn add_constraint(&mut self, constraint: Constraint) -> ConstraintReference {
-        let index = self.model.num_rows().try_into().unwrap();
+        let index = self.rows.len();
         let row = self.model.add_row();
-        let constant = -constraint.expression.constant;
+        self.rows.push(row);
+        let (lower, upper) = constraint.bounds();
         if constraint.is_equality {
-            self.model.set_row_equal(row, constant);
+            self.model.set_row_equal(row, upper);
         } else {
-            self.model.set_row_upper(row, constant);
+            self.model.set_row_upper(row, upper);
+            if constraint.is_ranged() {
+                self.model.set_row_lower(row, lower);
+            }
         }
         for (var, coeff) in constraint.expression.linear.coefficients.into_iter() {
             self.model.set_weight(row, self.columns[var.index()], coeff);
         }
-        ConstraintReference { index }
+        ConstraintReference::new(index, constraint.name)
     }
 
     fn name() -> &'static str {
@@ -166,6 +256,48 @@ impl SolverModel for CoinCbcProblem {
     }
 }
 
+impl ModifiableModel for CoinCbcProblem {
+    fn set_objective_coefficient(&mut self, variable: Variable, coefficient: f64) {
+        self.model
+            .set_obj_coeff(self.columns[variable.index()], coefficient);
+    }
+
+    fn set_variable_bounds(&mut self, variable: Variable, min: f64, max: f64) {
+        let col = self.columns[variable.index()];
+        self.model.set_col_lower(col, min);
+        self.model.set_col_upper(col, max);
+        self.column_bounds[variable.index()] = (min, max);
+    }
+
+    fn set_constraint_bounds(&mut self, constraint: &ConstraintReference, lower: f64, upper: f64) {
+        let row = self.rows[constraint.index];
+        self.model.set_row_lower(row, lower);
+        self.model.set_row_upper(row, upper);
+    }
+
+    /// Cbc cannot delete rows: the constraint is kept in the model, without bounds.
+    fn remove_constraint(&mut self, constraint: &ConstraintReference) {
+        self.set_constraint_bounds(constraint, f64::NEG_INFINITY, f64::INFINITY);
+    }
+
+    /// The previous solution is given to cbc as a starting point
+    fn resolve(&mut self) -> Result<CoinCbcSolution, ResolutionError> {
+        let solution = self.solve_model()?;
+        self.model.set_initial_solution(&solution.solution);
+        Ok(solution)
+    }
+}
+
+/// The variables that are not given a value start at zero
+impl WithInitialSolution for CoinCbcProblem {
+    fn set_initial_solution<I: IntoIterator<Item = (Variable, f64)>>(&mut self, solution: I) {
+        for (var, value) in solution {
+            self.model
+                .set_col_initial_solution(self.columns[var.index()], value);
+        }
+    }
+}
+
 /// Unfortunately, the current version of cbc silently ignores
 /// sos constraints on continuous variables.
 /// See <https://github.com/coin-or/Cbc/issues/376>
@@ -182,10 +314,29 @@ impl ModelWithSOS1 for CoinCbcProblem {
     }
 }
 
+/// Cardinality constraints are reformulated with binary indicator variables
+impl CardinalityConstraintSolver for CoinCbcProblem {
+    fn add_cardinality_constraint(&mut self, vars: &[Variable], rhs: usize) -> ConstraintReference {
+        let vars: Vec<_> = vars
+            .iter()
+            .map(|&var| (var, self.column_bounds[var.index()], self.add_binary()))
+            .collect();
+        add_cardinality_with_indicators(self, &vars, rhs)
+    }
+}
+
+/// Cbc reports an infinite objective value (COIN_DBL_MAX, or its 1e50 cutoff)
+/// when it did not find any feasible solution
+fn has_incumbent(raw: &coin_cbc::raw::Model) -> bool {
+    raw.obj_value().abs() < 1e50
+}
+
 /// A coin-cbc problem solution
 pub struct CoinCbcSolution {
     solution: CbcSolution,
     solution_vec: Vec<f64>, // See: rust-or/good_lp#6
+    status: SolutionStatus,
+    objective_constant: f64,
 }
 
 impl CoinCbcSolution {
@@ -200,6 +351,32 @@ impl Solution for CoinCbcSolution {
         // Our indices should always match those of cbc
         self.solution_vec[variable.index()]
     }
+
+    fn status(&self) -> SolutionStatus {
+        self.status
+    }
+}
+
+impl SolutionInfo for CoinCbcSolution {
+    fn objective_value(&self) -> f64 {
+        self.model().obj_value() + self.objective_constant
+    }
+
+    fn best_bound(&self) -> f64 {
+        if self.status.is_optimal() {
+            self.objective_value()
+        } else {
+            self.model().best_possible_value() + self.objective_constant
+        }
+    }
+}
+
+#[cfg(feature = "cbc-310")]
+#[cfg_attr(docsrs, doc(cfg(feature = "cbc-310")))]
+impl crate::solvers::SolutionWithReducedCosts for CoinCbcSolution {
+    fn reduced_cost(&self, variable: Variable) -> f64 {
+        self.solution.raw().reduced_cost()[variable.index()]
+    }
 }
 
 impl WithMipGap for CoinCbcProblem {
@@ -218,3 +395,103 @@ impl WithMipGap for CoinCbcProblem {
         }
     }
 }
+
+impl WithTimeLimit for CoinCbcProblem {
+    fn time_limit(&self) -> Option<f64> {
+        self.time_limit
+    }
+
+    fn with_time_limit(mut self, seconds: f64) -> Self {
+        self.time_limit = Some(seconds);
+        self
+    }
+}
+
+impl WithThreads for CoinCbcProblem {
+    fn threads(&self) -> Option<u32> {
+        self.threads
+    }
+
+    fn with_threads(mut self, threads: u32) -> Self {
+        self.threads = Some(threads);
+        self
+    }
+}
+
+impl WithVerbosity for CoinCbcProblem {
+    fn verbose(&self) -> bool {
+        !self.quiet
+    }
+
+    fn with_verbosity(mut self, verbose: bool) -> Self {
+        self.quiet = !verbose;
+        self
+    }
+}
+
+impl WithRandomSeed for CoinCbcProblem {
+    fn random_seed(&self) -> Option<u32> {
+        self.random_seed
+    }
+
+    fn with_random_seed(mut self, seed: u32) -> Self {
+        self.random_seed = Some(seed);
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        constraint, variable, variables, CardinalityConstraintSolver, ModelWithSOS1,
+        ModifiableModel, Solution, SolverModel,
+    };
+
+    use super::coin_cbc;
+
+    #[test]
+    fn can_add_binary_after_solving_with_sos() {
+        let mut vars = variables!();
+        let x = vars.add(variable().integer().clamp(0, 1));
+        let y = vars.add(variable().integer().clamp(0, 1));
+        let mut model = vars.minimise(-x - y).using(coin_cbc).with_sos1(x + 2 * y);
+        model.resolve().unwrap();
+        // The workaround for SOS constraints added columns to the cbc model
+        let binary = model.add_binary();
+        model.set_objective_coefficient(binary, -1.);
+        let solution = model.resolve().unwrap();
+        assert_eq!(solution.value(binary), 1.);
+    }
+
+    #[test]
+    fn can_add_cardinality_constraint_after_solving_with_sos() {
+        let mut vars = variables!();
+        let x = vars.add(variable().integer().clamp(0, 2));
+        let y = vars.add(variable().integer().clamp(0, 3));
+        let z = vars.add(variable().clamp(-2, 1));
+        let mut model = vars
+            .maximise(5 * x + 3 * y - 4 * z)
+            .using(coin_cbc)
+            .with_sos1(x + 2 * y);
+        let solution = model.resolve().unwrap();
+        assert_eq!((solution.value(x), solution.value(y)), (2., 0.));
+        model.add_cardinality_constraint(&[x, z], 1);
+        let solution = model.resolve().unwrap();
+        let values = (solution.value(x), solution.value(y), solution.value(z));
+        assert_eq!(values, (0., 3., -2.));
+    }
+
+    #[test]
+    fn can_change_constraint_added_after_solving_with_sos() {
+        let mut vars = variables!();
+        let x = vars.add(variable().integer().clamp(0, 2));
+        let y = vars.add(variable().integer().clamp(0, 3));
+        let mut model = vars.maximise(x + y).using(coin_cbc).with_sos1(x + 2 * y);
+        model.resolve().unwrap();
+        // The workaround for SOS constraints added a row to the cbc model
+        let constraint = model.add_constraint(constraint!(y <= 2));
+        model.set_constraint_bounds(&constraint, f64::NEG_INFINITY, 1.);
+        let solution = model.resolve().unwrap();
+        assert_eq!((solution.value(x), solution.value(y)), (2., 0.));
+    }
+}
@@ -1,25 +1,35 @@
 //! A solver that uses [highs](https://docs.rs/highs), a parallel C++ solver.
 
+use std::os::raw::c_void;
+use std::ptr::{null, null_mut};
+
 use highs::HighsModelStatus;
+use highs_sys::HighsInt;
 
+use crate::cardinality_constraint_solver_trait::add_cardinality_with_indicators;
+use crate::quadratic_expression::QuadraticTerms;
 use crate::solvers::{
-    MipGapError, ObjectiveDirection, ResolutionError, Solution, SolutionWithDual, SolverModel,
-    WithMipGap,
+    c_int_seed, default_best_bound, MipGapError, ModifiableModel, ObjectiveDirection,
+    ResolutionError, Solution, SolutionInfo, SolutionStatus, SolutionWithDual,
+    SolutionWithReducedCosts, SolverModel, WithInitialSolution, WithMipGap, WithRandomSeed,
+    WithThreads, WithTimeLimit, WithVerbosity,
 };
 use crate::{
     constraint::ConstraintReference,
     solvers::DualValues,
     variable::{UnsolvedProblem, VariableDefinition},
 };
-use crate::{Constraint, IntoAffineExpression, Variable};
+use crate::{
+    CardinalityConstraintSolver, Constraint, IntoAffineExpression, SensitivityRange,
+    SensitivityReport, SolutionWithSensitivity, Variable,
+};
 
 /// The [highs](https://docs.rs/highs) solver,
 /// to be used with [UnsolvedProblem::using].
 ///
-/// This solver does not support integer variables and will panic
-/// if given a problem with integer variables.
+/// The objective can be [quadratic](crate::QuadraticExpression),
+/// but HiGHS does not solve problems with both a quadratic objective and integer variables.
 pub fn highs(to_solve: UnsolvedProblem) -> HighsProblem {
-    let mut highs_problem = highs::RowProblem::default();
     let sense = match to_solve.direction {
         ObjectiveDirection::Maximisation => highs::Sense::Maximise,
         ObjectiveDirection::Minimisation => highs::Sense::Minimise,
@@ -41,15 +51,24 @@ pub fn highs(to_solve: UnsolvedProblem) -> HighsProblem {
             .coefficients
             .get(&var)
             .unwrap_or(&0.);
-        let col = highs_problem.add_column_with_integrality(col_factor, min..max, is_integer);
-        columns.push(col);
+        columns.push(HighsColumn {
+            col_factor,
+            min,
+            max,
+            is_integer,
+        });
     }
     HighsProblem {
         sense,
-        highs_problem,
+        objective_constant: to_solve.objective.constant,
         columns,
+        rows: vec![],
+        model_rows: 0,
         verbose: false,
         options: HighsOptions::default(),
+        model: None,
+        initial_solution: None,
+        quadratic_objective: to_solve.quadratic_objective,
     }
 }
 
@@ -123,8 +142,10 @@ struct HighsOptions {
     parallel: HighsParallelType,
     mip_abs_gap: Option<f32>,
     mip_rel_gap: Option<f32>,
-    time_limit: f64,
-    threads: u32,
+    time_limit: Option<f64>,
+    threads: Option<u32>,
+    random_seed: Option<u32>,
+    ranging: bool,
 }
 
 impl Default for HighsOptions {
@@ -135,26 +156,138 @@ impl Default for HighsOptions {
             parallel: HighsParallelType::Choose,
             mip_abs_gap: None,
             mip_rel_gap: None,
-            time_limit: f64::MAX,
-            threads: 0,
+            time_limit: None,
+            threads: None,
+            random_seed: None,
+            ranging: false,
         }
     }
 }
 
+/// A variable of the problem, as it will be given to HiGHS
+#[derive(Debug, Clone, Copy)]
+struct HighsColumn {
+    col_factor: f64,
+    min: f64,
+    max: f64,
+    is_integer: bool,
+}
+
+/// A constraint of the problem, as it will be given to HiGHS
+#[derive(Debug, Clone)]
+struct HighsRow {
+    lower: f64,
+    upper: f64,
+    factors: Vec<(usize, f64)>,
+    // the index of the row in the HiGHS model, None if the constraint was removed
+    position: Option<usize>,
+}
+
 /// A HiGHS model
+///
+/// The HiGHS model is created when the problem is first solved.
+/// It is then kept, and [modifications](ModifiableModel) are applied to it,
+/// so that the next [resolution](ModifiableModel::resolve) starts from the basis of the previous one.
 #[derive(Debug)]
 pub struct HighsProblem {
     sense: highs::Sense,
-    highs_problem: highs::RowProblem,
-    columns: Vec<highs::Col>,
+    objective_constant: f64,
+    columns: Vec<HighsColumn>,
+    // indexed by constraint reference, including the removed constraints
+    rows: Vec<HighsRow>,
+    // the number of rows that were not removed
+    model_rows: usize,
     verbose: bool,
     options: HighsOptions,
+    model: Option<highs::Model>,
+    // the values given with WithInitialSolution, infinite for the missing variables
+    initial_solution: Option<Vec<f64>>,
+    quadratic_objective: QuadraticTerms,
 }
 
 impl HighsProblem {
     /// Get a highs model for this problem
-    pub fn into_inner(self) -> highs::Model {
-        self.highs_problem.optimise(self.sense)
+    pub fn into_inner(mut self) -> highs::Model {
+        match self.model.take() {
+            Some(model) => model,
+            None => self.to_highs_model(),
+        }
+    }
+
+    fn to_highs_model(&self) -> highs::Model {
+        let mut problem = highs::RowProblem::default();
+        let cols: Vec<highs::Col> = self
+            .columns
+            .iter()
+            .map(|c| problem.add_column_with_integrality(c.col_factor, c.min..c.max, c.is_integer))
+            .collect();
+        for row in self.rows.iter().filter(|row| row.position.is_some()) {
+            let factors = row
+                .factors
+                .iter()
+                .map(|&(index, factor)| (cols[index], factor));
+            problem.add_row(row.lower..=row.upper, factors);
+        }
+        let mut model = problem.optimise(self.sense);
+        self.pass_hessian(&mut model);
+        model
+    }
+
+    /// HiGHS optimises `c'x + x'Qx / 2`, with the lower triangle of the Hessian Q
+    /// given column by column: a product of two different variables appears once,
+    /// and the coefficient of a square is doubled.
+    #[allow(unsafe_code)]
+    fn pass_hessian(&self, model: &mut highs::Model) {
+        let terms = self.quadratic_objective.sorted();
+        if terms.is_empty() {
+            return;
+        }
+        let mut start: Vec<HighsInt> = Vec::with_capacity(self.columns.len());
+        let mut index: Vec<HighsInt> = Vec::with_capacity(terms.len());
+        let mut value: Vec<f64> = Vec::with_capacity(terms.len());
+        for (column, row, coefficient) in terms {
+            while start.len() <= column.index() {
+                start.push(index.len() as HighsInt);
+            }
+            index.push(row.index() as HighsInt);
+            value.push(if column == row {
+                2. * coefficient
+            } else {
+                coefficient
+            });
+        }
+        start.resize(self.columns.len(), index.len() as HighsInt);
+        // SAFETY: start has one entry per column, index and value one per nonzero
+        let status = unsafe {
+            highs_sys::Highs_passHessian(
+                model.as_mut_ptr(),
+                self.columns.len() as HighsInt,
+                index.len() as HighsInt,
+                highs_sys::kHighsHessianFormatTriangular,
+                start.as_ptr(),
+                index.as_ptr(),
+                value.as_ptr(),
+            )
+        };
+        assert_ne!(
+            status,
+            highs_sys::STATUS_ERROR,
+            "HiGHS error in Highs_passHessian"
+        );
+    }
+
+    /// Calls a function of the HiGHS C API on the model, if it was already created.
+    /// Panics if HiGHS returns an error, like the highs crate does.
+    fn with_model(&mut self, function: &str, call: impl FnOnce(*mut c_void) -> HighsInt) {
+        if let Some(model) = &mut self.model {
+            let status = call(model.as_mut_ptr());
+            assert_ne!(
+                status,
+                highs_sys::STATUS_ERROR,
+                "HiGHS error in {}",
+                function
+            );
+        }
     }
 
     /// Sets whether or not HiGHS should display verbose logging information to the console
@@ -180,6 +313,14 @@ impl HighsProblem {
         self
     }
 
+    /// Sets whether HiGHS should compute the ranging information of the solutions,
+    /// from which their [sensitivity report](SolutionWithSensitivity) is made.
+    /// It is disabled by default, because it takes additional computations after each resolution.
+    pub fn set_ranging(mut self, ranging: bool) -> HighsProblem {
+        self.options.ranging = ranging;
+        self
+    }
+
     /// Sets HiGHS Tolerance on Absolute Gap Option
     pub fn set_mip_abs_gap(mut self, mip_abs_gap: f32) -> Result<HighsProblem, MipGapError> {
         if mip_abs_gap.is_sign_negative() {
@@ -205,15 +346,13 @@ impl HighsProblem {
     }
 
     /// Sets HiGHS Time Limit Option
-    pub fn set_time_limit(mut self, time_limit: f64) -> HighsProblem {
-        self.options.time_limit = time_limit;
-        self
+    pub fn set_time_limit(self, time_limit: f64) -> HighsProblem {
+        self.with_time_limit(time_limit)
     }
 
     /// Sets number of threads used by HiGHS
-    pub fn set_threads(mut self, threads: u32) -> HighsProblem {
-        self.options.threads = threads;
-        self
+    pub fn set_threads(self, threads: u32) -> HighsProblem {
+        self.with_threads(threads)
     }
 }
 
@@ -221,13 +360,78 @@ impl SolverModel for HighsProblem {
     type Solution = HighsSolution;
     type Error = ResolutionError;
 
-    fn solve(self) -> Result<Self::Solution, Self::Error> {
-        let verbose = self.verbose;
+    fn solve(mut self) -> Result<Self::Solution, Self::Error> {
+        self.solve_model()
+    }
+
+    #[allow(unsafe_code)]
+    fn add_constraint(&mut self, constraint: Constraint) -> ConstraintReference {
+        let index = self.rows.len();
+        let (lower, upper) = constraint.bounds();
+        let factors: Vec<(usize, f64)> = constraint
+            .expression
+            .linear_coefficients()
+            .map(|(variable, factor)| (variable.index(), factor))
+            .collect();
+        let (columns, values): (Vec<HighsInt>, Vec<f64>) = factors
+            .iter()
+            .map(|&(column, factor)| (column as HighsInt, factor))
+            .unzip();
+        // SAFETY: the arrays have the announced length and outlive the call
+        self.with_model("Highs_addRow", |highs| unsafe {
+            highs_sys::Highs_addRow(
+                highs,
+                lower,
+                upper,
+                columns.len() as HighsInt,
+                columns.as_ptr(),
+                values.as_ptr(),
+            )
+        });
+        self.rows.push(HighsRow {
+            lower,
+            upper,
+            factors,
+            position: Some(self.model_rows),
+        });
+        self.model_rows += 1;
+        ConstraintReference::new(index, constraint.name)
+    }
+
+    fn name() -> &'static str {
+        "Highs"
+    }
+}
+
+impl HighsProblem {
+    fn solve_model(&mut self) -> Result<HighsSolution, ResolutionError> {
+        if !self.quadratic_objective.is_empty() && self.columns.iter().any(|c| c.is_integer) {
+            return Err(ResolutionError::Other(
+                "HiGHS does not support quadratic objectives with integer variables",
+            ));
+        }
+        let mut model = match self.model.take() {
+            Some(model) => model,
+            None => self.to_highs_model(),
+        };
+        if let Some(initial) = &self.initial_solution {
+            // A starting point that HiGHS rejects is not a reason to stop
+            let _ = model.try_set_solution(Some(initial), None, None, None);
+        }
+        self.set_options(&mut model);
+        let mut solved = model.solve();
+        let solution = self.read_solution(&mut solved);
+        // The solved model keeps its basis, from which the next resolution starts
+        self.model = Some(solved.into());
+        solution
+    }
+
+    fn set_options(&self, model: &mut highs::Model) {
         let options = self.options;
-        let mut model = self.into_inner();
-        if verbose {
-            model.set_option(&b"output_flag"[..], true);
-            model.set_option(&b"log_to_console"[..], true);
+        // The verbosity can change between two resolutions
+        model.set_option(&b"output_flag"[..], self.verbose);
+        model.set_option(&b"log_to_console"[..], self.verbose);
+        if self.verbose {
             model.set_option(&b"log_dev_level"[..], 2);
         }
         model.set_option("presolve", options.presolve.as_str());
@@ -242,47 +446,341 @@ impl SolverModel for HighsProblem {
             model.set_option("mip_rel_gap", mip_rel_gap as f64);
         }
 
-        model.set_option("time_limit", options.time_limit);
-        model.set_option("threads", options.threads as i32);
-
-        let solved = model.solve();
-        match solved.status() {
-            HighsModelStatus::NotSet => Err(ResolutionError::Other("NotSet")),
-            HighsModelStatus::LoadError => Err(ResolutionError::Other("LoadError")),
-            HighsModelStatus::ModelError => Err(ResolutionError::Other("ModelError")),
-            HighsModelStatus::PresolveError => Err(ResolutionError::Other("PresolveError")),
-            HighsModelStatus::SolveError => Err(ResolutionError::Other("SolveError")),
-            HighsModelStatus::PostsolveError => Err(ResolutionError::Other("PostsolveError")),
-            HighsModelStatus::ModelEmpty => Err(ResolutionError::Other("ModelEmpty")),
-            HighsModelStatus::Infeasible => Err(ResolutionError::Infeasible),
-            HighsModelStatus::Unbounded => Err(ResolutionError::Unbounded),
-            HighsModelStatus::UnboundedOrInfeasible => Err(ResolutionError::Infeasible),
-            _ok_status => Ok(HighsSolution {
-                solution: solved.get_solution(),
-            }),
+        if let Some(time_limit) = options.time_limit {
+            model.set_option("time_limit", time_limit);
+        }
+
+        if let Some(threads) = options.threads {
+            model.set_option("threads", threads as i32);
+        }
+
+        if let Some(seed) = options.random_seed {
+            model.set_option("random_seed", c_int_seed(seed));
         }
     }
 
-    fn add_constraint(&mut self, constraint: Constraint) -> ConstraintReference {
-        let index = self.highs_problem.num_rows();
-        let upper_bound = -constraint.expression.constant();
-        let columns = &self.columns;
-        let factors = constraint
-            .expression
-            .linear_coefficients()
-            .into_iter()
-            .map(|(variable, factor)| (columns[variable.index()], factor));
-        if constraint.is_equality {
-            self.highs_problem
-                .add_row(upper_bound..=upper_bound, factors);
+    fn read_solution(
+        &self,
+        solved: &mut highs::SolvedModel,
+    ) -> Result<HighsSolution, ResolutionError> {
+        let options = self.options;
+        let objective_constant = self.objective_constant;
+        let direction = match self.sense {
+            highs::Sense::Maximise => ObjectiveDirection::Maximisation,
+            highs::Sense::Minimise => ObjectiveDirection::Minimisation,
+        };
+        let solution = solved.get_solution();
+        let status = match solved.status() {
+            HighsModelStatus::NotSet => return Err(ResolutionError::Other("NotSet")),
+            HighsModelStatus::LoadError => return Err(ResolutionError::Other("LoadError")),
+            HighsModelStatus::ModelError => return Err(ResolutionError::Other("ModelError")),
+            HighsModelStatus::PresolveError => return Err(ResolutionError::Other("PresolveError")),
+            HighsModelStatus::SolveError => return Err(ResolutionError::Other("SolveError")),
+            HighsModelStatus::PostsolveError => {
+                return Err(ResolutionError::Other("PostsolveError"))
+            }
+            HighsModelStatus::ModelEmpty => return Err(ResolutionError::Other("ModelEmpty")),
+            HighsModelStatus::Unknown => return Err(ResolutionError::Other("Unknown")),
+            HighsModelStatus::Infeasible => return Err(ResolutionError::Infeasible),
+            HighsModelStatus::Unbounded => return Err(ResolutionError::Unbounded),
+            HighsModelStatus::UnboundedOrInfeasible => return Err(ResolutionError::Infeasible),
+            // HiGHS considers that a solution within the requested gap is optimal
+            HighsModelStatus::Optimal => {
+                let gap = solved.mip_gap();
+                let gap_requested = options.mip_abs_gap.is_some() || options.mip_rel_gap.is_some();
+                if gap_requested && gap.is_finite() && gap > 0. {
+                    SolutionStatus::GapLimit
+                } else {
+                    SolutionStatus::Optimal
+                }
+            }
+            // When a limit is reached, the solution is only kept if it is feasible
+            _ if !self.is_feasible(&solution) => {
+                return Err(ResolutionError::Other(
+                    "A limit was reached before finding a feasible solution",
+                ))
+            }
+            HighsModelStatus::ReachedTimeLimit => SolutionStatus::TimeLimit,
+            HighsModelStatus::ReachedIterationLimit
+            | HighsModelStatus::ObjectiveBound
+            | HighsModelStatus::ObjectiveTarget => SolutionStatus::Feasible,
+        };
+        let objective_value = solved.objective_value() + objective_constant;
+        // HiGHS defines the gap as |objective - bound| / |objective|.
+        // It is infinite for problems without integer variables.
+        let gap = solved.mip_gap();
+        let best_bound = if gap.is_finite() {
+            let distance = gap * (objective_value - objective_constant).abs();
+            match direction {
+                ObjectiveDirection::Minimisation => objective_value - distance,
+                ObjectiveDirection::Maximisation => objective_value + distance,
+            }
+        } else {
+            default_best_bound(objective_value, status, direction)
+        };
+        let sensitivity = if options.ranging && status == SolutionStatus::Optimal {
+            self.ranging(solved, &solution)
         } else {
-            self.highs_problem.add_row(..=upper_bound, factors);
+            None
+        };
+        Ok(HighsSolution {
+            solution,
+            row_positions: self.rows.iter().map(|row| row.position).collect(),
+            sensitivity,
+            status,
+            objective_value,
+            best_bound,
+        })
+    }
+
+    /// The sensitivity report made from the ranging information of HiGHS,
+    /// which is only available for linear problems without integer variables
+    /// that were solved to optimality
+    #[allow(unsafe_code)]
+    fn ranging(
+        &self,
+        solved: &mut highs::SolvedModel,
+        solution: &highs::Solution,
+    ) -> Option<SensitivityReport> {
+        if !self.quadratic_objective.is_empty()
+            || self.columns.iter().any(|column| column.is_integer)
+        {
+            return None;
         }
-        ConstraintReference { index }
+        let mut cost_up = vec![0.; self.columns.len()];
+        let mut cost_down = vec![0.; self.columns.len()];
+        let mut bound_up = vec![0.; self.model_rows];
+        let mut bound_down = vec![0.; self.model_rows];
+        let mut column_status: Vec<HighsInt> = vec![0; self.columns.len()];
+        let mut row_status: Vec<HighsInt> = vec![0; self.model_rows];
+        let highs = solved.as_mut_ptr();
+        // SAFETY: the arrays have one value per column or row of the model,
+        // and HiGHS does not write the data that is given null pointers
+        let status = unsafe {
+            highs_sys::Highs_getRanging(
+                highs,
+                cost_up.as_mut_ptr(),
+                null_mut(),
+                null_mut(),
+                null_mut(),
+                cost_down.as_mut_ptr(),
+                null_mut(),
+                null_mut(),
+                null_mut(),
+                null_mut(),
+                null_mut(),
+                null_mut(),
+                null_mut(),
+                null_mut(),
+                null_mut(),
+                null_mut(),
+                null_mut(),
+                bound_up.as_mut_ptr(),
+                null_mut(),
+                null_mut(),
+                null_mut(),
+                bound_down.as_mut_ptr(),
+                null_mut(),
+                null_mut(),
+                null_mut(),
+            )
+        };
+        if status == highs_sys::STATUS_ERROR {
+            return None;
+        }
+        // SAFETY: the arrays have one value per column or row of the model
+        unsafe {
+            highs_sys::Highs_getBasis(highs, column_status.as_mut_ptr(), row_status.as_mut_ptr())
+        };
+        // HiGHS gives the limits of the ranges, in the direction of the objective
+        let objective = self
+            .columns
+            .iter()
+            .zip(cost_up.iter().zip(&cost_down))
+            .map(|(column, (&up, &down))| SensitivityRange {
+                allowable_decrease: column.col_factor - down,
+                allowable_increase: up - column.col_factor,
+            })
+            .collect();
+        let activities = solution.rows();
+        let constraints = self
+            .rows
+            .iter()
+            .map(|row| match row.position {
+                // A removed constraint can have any bound
+                None => SensitivityRange {
+                    allowable_decrease: f64::INFINITY,
+                    allowable_increase: f64::INFINITY,
+                },
+                // The dual value of a basic row stays zero until its bound reaches its value.
+                // HiGHS gives the range of the value of the row instead.
+                Some(position) if row_status[position] == highs_sys::kHighsBasisStatusBasic => {
+                    SensitivityRange {
+                        allowable_decrease: row.upper - activities[position],
+                        allowable_increase: f64::INFINITY,
+                    }
+                }
+                Some(position) => {
+                    let bound = if row_status[position] == highs_sys::kHighsBasisStatusLower {
+                        row.lower
+                    } else {
+                        row.upper
+                    };
+                    SensitivityRange {
+                        allowable_decrease: bound - bound_down[position],
+                        allowable_increase: bound_up[position] - bound,
+                    }
+                }
+            })
+            .collect();
+        Some(SensitivityReport::new(objective, constraints))
     }
 
-    fn name() -> &'static str {
-        "Highs"
+    /// Whether a solution respects the bounds, the integrality of the variables and the constraints.
+    /// The bindings do not give access to the primal solution status of HiGHS,
+    /// so it is checked with HiGHS's default MIP feasibility tolerance.
+    fn is_feasible(&self, solution: &highs::Solution) -> bool {
+        const TOLERANCE: f64 = 1e-6;
+        let within =
+            |value: f64, min: f64, max: f64| value >= min - TOLERANCE && value <= max + TOLERANCE;
+        let columns = solution.columns();
+        let rows = solution.rows();
+        columns.len() == self.columns.len()
+            && rows.len() == self.model_rows
+            && self.columns.iter().zip(columns).all(|(column, &value)| {
+                within(value, column.min, column.max)
+                    && (!column.is_integer || (value - value.round()).abs() <= TOLERANCE)
+            })
+            && self
+                .rows
+                .iter()
+                .filter_map(|row| Some((row, rows[row.position?])))
+                .all(|(row, value)| within(value, row.lower, row.upper))
+    }
+}
+
+/// The changes are applied to the HiGHS model, which keeps its basis between two resolutions
+impl ModifiableModel for HighsProblem {
+    #[allow(unsafe_code)]
+    fn set_objective_coefficient(&mut self, variable: Variable, coefficient: f64) {
+        self.columns[variable.index()].col_factor = coefficient;
+        let column = variable.index() as HighsInt;
+        // SAFETY: the column exists in the model
+        self.with_model("Highs_changeColCost", |highs| unsafe {
+            highs_sys::Highs_changeColCost(highs, column, coefficient)
+        });
+    }
+
+    #[allow(unsafe_code)]
+    fn set_variable_bounds(&mut self, variable: Variable, min: f64, max: f64) {
+        let column = &mut self.columns[variable.index()];
+        column.min = min;
+        column.max = max;
+        let column = variable.index() as HighsInt;
+        // SAFETY: the column exists in the model
+        self.with_model("Highs_changeColBounds", |highs| unsafe {
+            highs_sys::Highs_changeColBounds(highs, column, min, max)
+        });
+    }
+
+    #[allow(unsafe_code)]
+    fn set_constraint_bounds(&mut self, constraint: &ConstraintReference, lower: f64, upper: f64) {
+        let row = &mut self.rows[constraint.index];
+        row.lower = lower;
+        row.upper = upper;
+        if let Some(position) = row.position {
+            // SAFETY: the row exists in the model
+            self.with_model("Highs_changeRowBounds", |highs| unsafe {
+                highs_sys::Highs_changeRowBounds(highs, position as HighsInt, lower, upper)
+            });
+        }
+    }
+
+    /// The row is deleted from the HiGHS model, and the rows after it are renumbered.
+    /// The references to the other constraints remain valid.
+    #[allow(unsafe_code)]
+    fn remove_constraint(&mut self, constraint: &ConstraintReference) {
+        if let Some(position) = self.rows[constraint.index].position.take() {
+            for row in &mut self.rows[constraint.index + 1..] {
+                if let Some(next) = &mut row.position {
+                    *next -= 1;
+                }
+            }
+            self.model_rows -= 1;
+            let set = [position as HighsInt];
+            // SAFETY: the set contains one existing row
+            self.with_model("Highs_deleteRowsBySet", |highs| unsafe {
+                highs_sys::Highs_deleteRowsBySet(highs, 1, set.as_ptr())
+            });
+        }
+    }
+
+    /// HiGHS starts from the basis found by the previous resolution
+    fn resolve(&mut self) -> Result<HighsSolution, ResolutionError> {
+        let solution = self.solve_model()?;
+        self.initial_solution = None;
+        Ok(solution)
+    }
+}
+
+/// Cardinality constraints are reformulated with binary indicator variables
+impl CardinalityConstraintSolver for HighsProblem {
+    fn add_cardinality_constraint(&mut self, vars: &[Variable], rhs: usize) -> ConstraintReference {
+        let vars: Vec<_> = vars
+            .iter()
+            .map(|&var| {
+                let bounds = (self.columns[var.index()].min, self.columns[var.index()].max);
+                (var, bounds, self.add_binary())
+            })
+            .collect();
+        add_cardinality_with_indicators(self, &vars, rhs)
+    }
+}
+
+impl HighsProblem {
+    /// Adds a binary variable that is not in the objective
+    #[allow(unsafe_code)]
+    fn add_binary(&mut self) -> Variable {
+        let column = self.columns.len();
+        self.columns.push(HighsColumn {
+            col_factor: 0.,
+            min: 0.,
+            max: 1.,
+            is_integer: true,
+        });
+        if let Some(values) = &mut self.initial_solution {
+            values.push(f64::INFINITY);
+        }
+        // SAFETY: the new column has no coefficient, so no array is read
+        self.with_model("Highs_addCol", |highs| unsafe {
+            highs_sys::Highs_addCol(highs, 0., 0., 1., 0, null(), null())
+        });
+        // SAFETY: the column was just added
+        self.with_model("Highs_changeColIntegrality", |highs| unsafe {
+            highs_sys::Highs_changeColIntegrality(
+                highs,
+                column as HighsInt,
+                highs_sys::VAR_TYPE_INTEGER,
+            )
+        });
+        Variable::at(column)
+    }
+}
+
+/// HiGHS tries to complete a partial initial solution by fixing the integer variables
+/// that were given a value, and solving the remaining problem.
+/// After a [resolution](ModifiableModel::resolve), HiGHS starts from
+/// the solution that was found instead.
+impl WithInitialSolution for HighsProblem {
+    fn set_initial_solution<I: IntoIterator<Item = (Variable, f64)>>(&mut self, solution: I) {
+        let n_columns = self.columns.len();
+        // HiGHS considers infinite values as undefined
+        let values = self
+            .initial_solution
+            .get_or_insert_with(|| vec![f64::INFINITY; n_columns]);
+        for (var, value) in solution {
+            values[var.index()] = value;
+        }
     }
 }
 
@@ -290,10 +788,18 @@ impl SolverModel for HighsProblem {
 #[derive(Debug)]
 pub struct HighsSolution {
     solution: highs::Solution,
+    // the index of each constraint in the solution, None for the removed constraints
+    row_positions: Vec<Option<usize>>,
+    // computed when ranging is enabled, for linear problems solved to optimality
+    sensitivity: Option<SensitivityReport>,
+    status: SolutionStatus,
+    objective_value: f64,
+    best_bound: f64,
 }
 
 impl HighsSolution {
-    /// Returns the highs solution object. You can use it to fetch dual values
+    /// Returns the highs solution object. You can use it to fetch dual values.
+    /// Its rows do not include the [removed](ModifiableModel::remove_constraint) constraints.
     pub fn into_inner(self) -> highs::Solution {
         self.solution
     }
@@ -303,11 +809,33 @@ impl Solution for HighsSolution {
     fn value(&self, variable: Variable) -> f64 {
         self.solution.columns()[variable.index()]
     }
+
+    fn status(&self) -> SolutionStatus {
+        self.status
+    }
+}
+
+impl SolutionInfo for HighsSolution {
+    fn objective_value(&self) -> f64 {
+        self.objective_value
+    }
+
+    fn best_bound(&self) -> f64 {
+        self.best_bound
+    }
 }
 
 impl<'a> DualValues for &'a HighsSolution {
+    /// The dual value of a removed constraint is zero
     fn dual(&self, constraint: ConstraintReference) -> f64 {
-        self.solution.dual_rows()[constraint.index]
+        self.row_positions[constraint.index]
+            .map_or(0., |position| self.solution.dual_rows()[position])
+    }
+}
+
+impl SolutionWithReducedCosts for HighsSolution {
+    fn reduced_cost(&self, variable: Variable) -> f64 {
+        self.solution.dual_columns()[variable.index()]
     }
 }
 
@@ -319,6 +847,25 @@ impl<'a> SolutionWithDual<'a> for HighsSolution {
     }
 }
 
+/// The sensitivity report is made from the ranging information that HiGHS computes
+/// on the optimal basis, when it was enabled with [HighsProblem::set_ranging].
+/// HiGHS does not compute it for problems with integer variables or a quadratic objective.
+/// The ranges are NaN when it is not available.
+impl SolutionWithSensitivity for HighsSolution {
+    fn sensitivity_report(&self) -> SensitivityReport {
+        self.sensitivity.clone().unwrap_or_else(|| {
+            let unknown = SensitivityRange {
+                allowable_decrease: f64::NAN,
+                allowable_increase: f64::NAN,
+            };
+            SensitivityReport::new(
+                vec![unknown; self.solution.columns().len()],
+                vec![unknown; self.row_positions.len()],
+            )
+        })
+    }
+}
+
 impl WithMipGap for HighsProblem {
     fn mip_gap(&self) -> Option<f32> {
         self.options.mip_rel_gap
@@ -328,3 +875,47 @@ impl WithMipGap for HighsProblem {
         self.set_mip_rel_gap(mip_gap)
     }
 }
+
+impl WithTimeLimit for HighsProblem {
+    fn time_limit(&self) -> Option<f64> {
+        self.options.time_limit
+    }
+
+    fn with_time_limit(mut self, seconds: f64) -> Self {
+        self.options.time_limit = Some(seconds);
+        self
+    }
+}
+
+impl WithThreads for HighsProblem {
+    fn threads(&self) -> Option<u32> {
+        self.options.threads
+    }
+
+    fn with_threads(mut self, threads: u32) -> Self {
+        self.options.threads = Some(threads);
+        self
+    }
+}
+
+impl WithVerbosity for HighsProblem {
+    fn verbose(&self) -> bool {
+        self.verbose
+    }
+
+    fn with_verbosity(mut self, verbose: bool) -> Self {
+        self.set_verbose(verbose);
+        self
+    }
+}
+
+impl WithRandomSeed for HighsProblem {
+    fn random_seed(&self) -> Option<u32> {
+        self.options.random_seed
+    }
+
+    fn with_random_seed(mut self, seed: u32) -> Self {
+        self.options.random_seed = Some(seed);
+        self
+    }
+}
@@ -10,7 +10,10 @@ pub use lp_solvers::solvers::*;
 use lp_solvers::util::UniqueNameGenerator;
 
 use crate::constraint::ConstraintReference;
-use crate::solvers::{MipGapError, ObjectiveDirection};
+use crate::solvers::{
+    default_best_bound, MipGapError, ObjectiveDirection, SolutionInfo, SolutionStatus, WithThreads,
+    WithTimeLimit, UNSUPPORTED_QUADRATIC_OBJECTIVE,
+};
 use crate::variable::UnsolvedProblem;
 use crate::{
     Constraint, Expression, IntoAffineExpression, ResolutionError, Solution as GoodLpSolution,
@@ -49,7 +52,11 @@ impl<T: lp_solvers::solvers::SolverTrait + Clone> Solver for LpSolver<T> {
                 variables,
                 constraints: vec![],
             },
+            constraint_count: 0,
             solver: self.0.clone(),
+            objective: problem.objective,
+            direction: problem.direction,
+            has_quadratic_objective: !problem.quadratic_objective.is_empty(),
         }
     }
 
@@ -85,10 +92,50 @@ where
     }
 }
 
+/// External solvers take a whole number of seconds: the time limit is rounded up
+impl<T> WithTimeLimit for Model<T>
+where
+    T: WithMaxSeconds<T>,
+{
+    fn time_limit(&self) -> Option<f64> {
+        self.solver.max_seconds().map(f64::from)
+    }
+
+    fn with_time_limit(mut self, seconds: f64) -> Self {
+        self.solver = self.solver.with_max_seconds(seconds.ceil() as u32);
+        self
+    }
+}
+
+impl<T> WithThreads for Model<T>
+where
+    T: WithNbThreads<T>,
+{
+    fn threads(&self) -> Option<u32> {
+        self.solver.nb_threads()
+    }
+
+    fn with_threads(mut self, threads: u32) -> Self {
+        self.solver = self.solver.with_nb_threads(threads);
+        self
+    }
+}
+
 /// A problem to be used by lp-solvers
+///
+/// Ranged constraints are written as two rows, a `>=` row followed by a `<=` row,
+/// so the rows of the problem do not match the constraints one to one.
+/// The [references](ConstraintReference) returned by [SolverModel::add_constraint]
+/// count each constraint once, in the order they were added.
 pub struct Model<T> {
     problem: lp_solvers::problem::Problem,
+    // The number of constraints added, that differs from the number of rows
+    constraint_count: usize,
     solver: T,
+    objective: Expression,
+    direction: ObjectiveDirection,
+    // Solving fails if the objective is quadratic
+    has_quadratic_objective: bool,
 }
 
 impl<T: SolverTrait> SolverModel for Model<T> {
@@ -96,36 +143,58 @@ impl<T: SolverTrait> SolverModel for Model<T> {
     type Error = ResolutionError;
 
     fn solve(self) -> Result<Self::Solution, Self::Error> {
+        if self.has_quadratic_objective {
+            return Err(UNSUPPORTED_QUADRATIC_OBJECTIVE);
+        }
         let map = self.solver.run(&self.problem)?;
-        match map.status {
+        let status = match map.status {
             Status::Infeasible => return Err(ResolutionError::Infeasible),
             Status::Unbounded => return Err(ResolutionError::Unbounded),
             Status::NotSolved => return Err(ResolutionError::Other("unknown error: not solved")),
-            _ => {}
-        }
+            Status::Optimal => SolutionStatus::Optimal,
+            Status::MipGap => SolutionStatus::GapLimit,
+            Status::TimeLimit => SolutionStatus::TimeLimit,
+            Status::SubOptimal => SolutionStatus::Feasible,
+        };
         let solution = self
             .problem
             .variables
             .iter()
             .map(|v| f64::from(*map.results.get(&v.name).unwrap_or(&0.)))
             .collect();
-        Ok(LpSolution { solution })
+        Ok(LpSolution {
+            solution,
+            status,
+            objective: self.objective,
+            direction: self.direction,
+        })
     }
 
     fn add_constraint(&mut self, c: Constraint) -> ConstraintReference {
-        let reference = ConstraintReference {
-            index: self.problem.constraints.len(),
-        };
+        let (lower, upper) = c.bounds();
+        let lhs = linear_coefficients_str(&c.expression, &self.problem.variables);
+        // Ranged constraints are written as two rows
+        if c.is_ranged() {
+            self.problem
+                .constraints
+                .push(lp_solvers::lp_format::Constraint {
+                    lhs: linear_coefficients_str(&c.expression, &self.problem.variables),
+                    operator: Ordering::Greater,
+                    rhs: lower,
+                });
+        }
+        let reference = ConstraintReference::new(self.constraint_count, c.name);
+        self.constraint_count += 1;
         self.problem
             .constraints
             .push(lp_solvers::lp_format::Constraint {
-                lhs: linear_coefficients_str(&c.expression, &self.problem.variables),
+                lhs,
                 operator: if c.is_equality {
                     Ordering::Equal
                 } else {
                     Ordering::Less
                 },
-                rhs: -c.expression.constant,
+                rhs: upper,
             });
         reference
     }
@@ -150,18 +219,37 @@ fn linear_coefficients_str(
 /// A solution
 pub struct LpSolution {
     solution: Vec<f64>,
+    status: SolutionStatus,
+    objective: Expression,
+    direction: ObjectiveDirection,
 }
 
 impl GoodLpSolution for LpSolution {
     fn value(&self, variable: Variable) -> f64 {
         self.solution[variable.index()]
     }
+
+    fn status(&self) -> SolutionStatus {
+        self.status
+    }
+}
+
+/// External solvers don't report the bound they reached,
+/// so only optimal solutions have a finite [best bound](SolutionInfo::best_bound).
+impl SolutionInfo for LpSolution {
+    fn objective_value(&self) -> f64 {
+        self.eval(&self.objective)
+    }
+
+    fn best_bound(&self) -> f64 {
+        default_best_bound(self.objective_value(), self.status, self.direction)
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use crate::solvers::lp_solvers::{GlpkSolver, LpSolver};
-    use crate::variables;
+    use crate::{constraint, variables, SolverModel};
 
     #[test]
     fn coefficient_formatting_pos_pos() {
@@ -198,4 +286,15 @@ mod tests {
             .using(LpSolver(GlpkSolver::new()));
         assert_eq!(problem.problem.objective.0, "-2 b -1 a");
     }
+
+    #[test]
+    fn references_after_ranged_constraint() {
+        variables! {vars: a; b; }
+        let mut problem = vars.minimise(a + b).using(LpSolver(GlpkSolver::new()));
+        let ranged = problem.add_constraint(constraint!(1 <= a + b <= 4).named("ranged"));
+        let next = problem.add_constraint(constraint!(a - b <= 2).named("next"));
+        assert_eq!((ranged.index, ranged.name()), (0, Some("ranged")));
+        assert_eq!((next.index, next.name()), (1, Some("next")));
+        assert_eq!(problem.problem.constraints.len(), 3);
+    }
 }
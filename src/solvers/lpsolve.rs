@@ -1,16 +1,30 @@
 //! A solver that uses a [Cbc](https://www.coin-or.org/Cbc/) [native library binding](https://docs.rs/coin_cbc).
 //! This solver is activated using the default `coin_cbc` feature.
 //! You can disable it an enable another solver instead using cargo features.
-use crate::solvers::{ObjectiveDirection, ResolutionError, Solution, SolverModel};
+use crate::cardinality_constraint_solver_trait::add_cardinality_with_indicators;
+use crate::solvers::{
+    default_best_bound, DualValues, ModifiableModel, ObjectiveDirection, ResolutionError, Solution,
+    SolutionInfo, SolutionStatus, SolutionWithDual, SolutionWithReducedCosts, SolverModel,
+    WithTimeLimit, WithVerbosity, UNSUPPORTED_QUADRATIC_OBJECTIVE,
+};
 use crate::variable::UnsolvedProblem;
 use crate::{
     affine_expression_trait::IntoAffineExpression, constraint::ConstraintReference, ModelWithSOS1,
+    ModelWithSOS2,
+};
+use crate::{
+    CardinalityConstraintSolver, Constraint, Expression, SensitivityRange, SensitivityReport,
+    SolutionWithSensitivity, Variable,
 };
-use crate::{Constraint, Variable};
 use lpsolve::{ConstraintType, Problem, SOSType, SolveStatus};
 use std::convert::TryInto;
 use std::ffi::CString;
-use std::os::raw::c_int;
+use std::os::raw::{c_int, c_long};
+use std::ptr::null_mut;
+
+/// The verbosity levels of lp_solve
+const NEUTRAL: c_int = 0;
+const NORMAL: c_int = 4;
 
 fn expr_to_scatter_vec<E: IntoAffineExpression>(expr: E) -> (Vec<f64>, Vec<c_int>, f64) {
     let constant = expr.constant();
@@ -31,48 +45,136 @@ fn col_num(var: Variable) -> c_int {
     to_c(var.index() + 1)
 }
 
+fn set_column_bounds(problem: &mut Problem, col: c_int, min: f64, max: f64) {
+    if min.is_finite() || max.is_finite() {
+        assert!(problem.set_bounds(col, min, max));
+    } else {
+        assert!(problem.set_unbounded(col));
+    }
+}
+
 /// The [lp_solve](http://lpsolve.sourceforge.net/5.5/) open-source solver library.
 /// lp_solve is released under the LGPL license.
 pub fn lp_solve(to_solve: UnsolvedProblem) -> LpSolveProblem {
     let UnsolvedProblem {
         objective,
+        quadratic_objective,
         direction,
         variables,
     } = to_solve;
 
     // It looks like the lp_solve rust binding doesn't expose the set_maxim function
-    let objective = if direction == ObjectiveDirection::Minimisation {
-        objective
+    let minimised = if direction == ObjectiveDirection::Minimisation {
+        objective.clone()
     } else {
-        -objective
+        -objective.clone()
     };
 
     let cols = to_c(variables.len());
     let mut model = Problem::new(0, cols).expect("Unable to create problem");
-    let (obj_coefs, obj_idx, _const) = expr_to_scatter_vec(objective);
+    let (obj_coefs, obj_idx, _const) = expr_to_scatter_vec(minimised);
     assert!(model.scatter_objective_function(&obj_coefs, &obj_idx));
+    let column_bounds = variables
+        .iter_variables_with_def()
+        .map(|(_, def)| (def.min, def.max))
+        .collect();
     for (i, v) in variables.into_iter().enumerate() {
         let col = to_c(i + 1);
         assert!(model.set_integer(col, v.is_integer));
-        if v.min.is_finite() || v.max.is_finite() {
-            assert!(model.set_bounds(col, v.min, v.max));
-        } else {
-            assert!(model.set_unbounded(col));
-        }
+        set_column_bounds(&mut model, col, v.min, v.max);
+    }
+    LpSolveProblem {
+        problem: model,
+        objective,
+        direction,
+        time_limit: None,
+        verbose: true,
+        column_bounds,
+        rows: vec![],
+        basis: None,
+        has_quadratic_objective: !quadratic_objective.is_empty(),
     }
-    LpSolveProblem(model)
 }
 
 /// An lp_solve problem instance
-pub struct LpSolveProblem(Problem);
+///
+/// lp_solve has no option for the number of threads nor for a random seed,
+/// so this model does not implement [WithThreads](crate::WithThreads)
+/// nor [WithRandomSeed](crate::WithRandomSeed).
+pub struct LpSolveProblem {
+    problem: Problem,
+    objective: Expression,
+    direction: ObjectiveDirection,
+    time_limit: Option<f64>,
+    // lp_solve displays its logs by default
+    verbose: bool,
+    // The binding does not give access to the bounds of the columns
+    column_bounds: Vec<(f64, f64)>,
+    // the lp_solve row of each constraint, None for the removed constraints.
+    // The free rows added for the SOS constraints are not in it.
+    rows: Vec<Option<c_int>>,
+    // Solving fails if the objective is quadratic
+    has_quadratic_objective: bool,
+    // the basis of the last resolution, from which the next one starts
+    basis: Option<Vec<c_int>>,
+}
 
 impl SolverModel for LpSolveProblem {
     type Solution = LpSolveSolution;
     type Error = ResolutionError;
 
     fn solve(mut self) -> Result<Self::Solution, Self::Error> {
+        let (status, solution) = self.solve_problem()?;
+        Ok(LpSolveSolution {
+            problem: self.problem,
+            rows: self.rows,
+            solution,
+            status,
+            objective: self.objective,
+            direction: self.direction,
+        })
+    }
+
+    fn add_constraint(&mut self, constraint: Constraint) -> ConstraintReference {
+        let index = self.rows.len();
+        // lp_solve rows are numbered from 1
+        let row = self.problem.num_rows() + 1;
+        let mut coeffs: Vec<f64> = vec![0.; self.problem.num_cols() as usize + 1];
+        let (lower, target) = constraint.bounds();
+        let is_ranged = constraint.is_ranged();
+        for (var, coeff) in constraint.expression.linear_coefficients() {
+            coeffs[var.index() + 1] = coeff;
+        }
+        let constraint_type = if constraint.is_equality {
+            ConstraintType::Eq
+        } else {
+            ConstraintType::Le
+        };
+        let success = self
+            .problem
+            .add_constraint(&coeffs, target, constraint_type);
+        assert!(success, "could not add constraint. memory error.");
+        if is_ranged {
+            let success = self.problem.set_constraint_range(row, target - lower);
+            assert!(success, "could not set the range of the constraint");
+        }
+        self.rows.push(Some(row));
+        ConstraintReference::new(index, constraint.name)
+    }
+
+    fn name() -> &'static str {
+        "lp_solve"
+    }
+}
+
+impl LpSolveProblem {
+    fn solve_problem(&mut self) -> Result<(SolutionStatus, Vec<f64>), ResolutionError> {
+        if self.has_quadratic_objective {
+            return Err(UNSUPPORTED_QUADRATIC_OBJECTIVE);
+        }
+        self.restore_basis();
         use ResolutionError::*;
-        match Problem::solve(&mut self.0) {
+        let status = match Problem::solve(&mut self.problem) {
             SolveStatus::Unbounded => Err(Unbounded),
             SolveStatus::Infeasible => Err(Infeasible),
             SolveStatus::OutOfMemory => Err(Other("OutOfMemory")),
@@ -84,67 +186,233 @@ impl SolverModel for LpSolveProblem {
             SolveStatus::ProcFail => Err(Other("ProcFail")),
             SolveStatus::ProcBreak => Err(Other("ProcBreak")),
             SolveStatus::NoFeasibleFound => Err(Other("NoFeasibleFound")),
-            _ => {
-                let mut solution = vec![0.; self.0.num_cols() as usize];
-                let truncated = self
-                    .0
-                    .get_solution_variables(&mut solution)
-                    .expect("internal error: invalid solution array length");
-                assert_eq!(
-                    truncated.len(),
-                    solution.len(),
-                    "The solution doesn't have the expected number of variables"
-                );
-                Ok(LpSolveSolution {
-                    problem: self.0,
-                    solution,
-                })
+            // Branch and bound stopped early, and the only limit that can be set is the time
+            SolveStatus::Suboptimal if self.time_limit.is_some() => Ok(SolutionStatus::TimeLimit),
+            SolveStatus::Suboptimal | SolveStatus::FeasibleFound => Ok(SolutionStatus::Feasible),
+            SolveStatus::Optimal | SolveStatus::Presolved => Ok(SolutionStatus::Optimal),
+        }?;
+        let mut solution = vec![0.; self.problem.num_cols() as usize];
+        let truncated = self
+            .problem
+            .get_solution_variables(&mut solution)
+            .expect("internal error: invalid solution array length");
+        assert_eq!(
+            truncated.len(),
+            solution.len(),
+            "The solution doesn't have the expected number of variables"
+        );
+        Ok((status, solution))
+    }
+
+    /// Saves the basis of the last resolution.
+    /// It cannot be kept in a copy of the model: lp_solve does not update the basis
+    /// of a model that was never solved when rows are added to it.
+    #[allow(unsafe_code)]
+    fn save_basis(&mut self) {
+        let mut basis: Vec<c_int> = vec![0; self.basis_size()];
+        // SAFETY: with the non-basic variables, the basis has an entry per row and column, plus one
+        let saved =
+            unsafe { lpsolve_sys::get_basis(self.problem.to_lprec(), basis.as_mut_ptr(), 1) };
+        self.basis = Some(basis).filter(|_| saved == 1);
+    }
+
+    #[allow(unsafe_code)]
+    fn restore_basis(&mut self) {
+        let size = self.basis_size();
+        // A basis saved before rows or columns were added or removed is of no use
+        if let Some(mut basis) = self.basis.take().filter(|basis| basis.len() == size) {
+            // SAFETY: the basis has an entry per row and column, plus one.
+            // lp_solve starts from scratch if it rejects the basis.
+            unsafe { lpsolve_sys::set_basis(self.problem.to_lprec(), basis.as_mut_ptr(), 1) };
+        }
+    }
+
+    fn basis_size(&self) -> usize {
+        (1 + self.problem.num_rows() + self.problem.num_cols()) as usize
+    }
+}
+
+/// lp_solve starts each resolution from the basis of the previous one.
+/// The solution keeps the solved model, and the changes are applied to a copy of it.
+impl ModifiableModel for LpSolveProblem {
+    #[allow(unsafe_code)]
+    fn set_objective_coefficient(&mut self, variable: Variable, coefficient: f64) {
+        self.objective
+            .linear
+            .coefficients
+            .insert(variable, coefficient);
+        // The objective is negated for maximisation
+        let minimised = match self.direction {
+            ObjectiveDirection::Minimisation => coefficient,
+            ObjectiveDirection::Maximisation => -coefficient,
+        };
+        // SAFETY: the lprec pointer is valid while the problem lives, and row 0 is the objective
+        let success = unsafe {
+            lpsolve_sys::set_mat(self.problem.to_lprec(), 0, col_num(variable), minimised)
+        };
+        assert_eq!(success, 1, "could not set the objective coefficient");
+    }
+
+    fn set_variable_bounds(&mut self, variable: Variable, min: f64, max: f64) {
+        set_column_bounds(&mut self.problem, col_num(variable), min, max);
+        self.column_bounds[variable.index()] = (min, max);
+    }
+
+    /// A row only changes type when its new bounds require it,
+    /// because lp_solve then discards the basis of the previous resolution.
+    #[allow(unsafe_code)]
+    fn set_constraint_bounds(&mut self, constraint: &ConstraintReference, lower: f64, upper: f64) {
+        if let Some(row) = self.rows[constraint.index] {
+            // A `<=` row can only have a lower bound through its range
+            let (constraint_type, rh) = if lower == upper {
+                (ConstraintType::Eq, upper)
+            } else if upper.is_finite() || lower.is_infinite() {
+                (ConstraintType::Le, upper)
+            } else {
+                (ConstraintType::Ge, lower)
+            };
+            let is_le = constraint_type == ConstraintType::Le;
+            if self.problem.get_constraint_type(row).as_ref() != Some(&constraint_type) {
+                assert!(self.problem.set_constraint_type(row, constraint_type));
+            }
+            // SAFETY: the lprec pointer is valid while the problem lives, and the row exists
+            let success = unsafe { lpsolve_sys::set_rh(self.problem.to_lprec(), row, rh) };
+            assert_eq!(success, 1, "could not set the bound of the constraint");
+            if is_le {
+                // An infinite range removes the lower bound
+                assert!(self.problem.set_constraint_range(row, upper - lower));
             }
         }
     }
 
-    fn add_constraint(&mut self, constraint: Constraint) -> ConstraintReference {
-        let index = self.0.num_rows().try_into().expect("too many rows");
-        let mut coeffs: Vec<f64> = vec![0.; self.0.num_cols() as usize + 1];
-        let target = -constraint.expression.constant;
-        for (var, coeff) in constraint.expression.linear_coefficients() {
-            coeffs[var.index() + 1] = coeff;
+    /// The row is deleted from the lp_solve model, and the rows after it are renumbered.
+    /// The references to the other constraints remain valid.
+    fn remove_constraint(&mut self, constraint: &ConstraintReference) {
+        if let Some(row) = self.rows[constraint.index].take() {
+            assert!(self.problem.del_constraint(row));
+            for other in self.rows.iter_mut().flatten() {
+                if *other > row {
+                    *other -= 1;
+                }
+            }
         }
-        let constraint_type = if constraint.is_equality {
-            ConstraintType::Eq
-        } else {
-            ConstraintType::Le
-        };
-        let success = self.0.add_constraint(&coeffs, target, constraint_type);
-        assert!(success, "could not add constraint. memory error.");
-        ConstraintReference { index }
     }
 
-    fn name() -> &'static str {
-        "lp_solve"
+    fn resolve(&mut self) -> Result<LpSolveSolution, ResolutionError> {
+        let (status, solution) = self.solve_problem()?;
+        self.save_basis();
+        let copy = self.problem.clone();
+        Ok(LpSolveSolution {
+            problem: std::mem::replace(&mut self.problem, copy),
+            rows: self.rows.clone(),
+            solution,
+            status,
+            objective: self.objective.clone(),
+            direction: self.direction,
+        })
+    }
+}
+
+/// lp_solve takes a whole number of seconds: the time limit is rounded up
+impl WithTimeLimit for LpSolveProblem {
+    fn time_limit(&self) -> Option<f64> {
+        self.time_limit
+    }
+
+    #[allow(unsafe_code)]
+    fn with_time_limit(mut self, seconds: f64) -> Self {
+        let whole_seconds = seconds.ceil() as c_long;
+        // SAFETY: the lprec pointer is valid while the problem lives
+        unsafe { lpsolve_sys::set_timeout(self.problem.to_lprec(), whole_seconds) };
+        self.time_limit = Some(seconds);
+        self
+    }
+}
+
+impl WithVerbosity for LpSolveProblem {
+    fn verbose(&self) -> bool {
+        self.verbose
+    }
+
+    #[allow(unsafe_code)]
+    fn with_verbosity(mut self, verbose: bool) -> Self {
+        let level = if verbose { NORMAL } else { NEUTRAL };
+        // SAFETY: the lprec pointer is valid while the problem lives
+        unsafe { lpsolve_sys::set_verbose(self.problem.to_lprec(), level) };
+        self.verbose = verbose;
+        self
     }
 }
 
 impl ModelWithSOS1 for LpSolveProblem {
     fn add_sos1<I: IntoAffineExpression>(&mut self, variables: I) {
+        self.add_sos(SOSType::Type1, variables)
+    }
+}
+
+impl ModelWithSOS2 for LpSolveProblem {
+    fn add_sos2<I: IntoAffineExpression>(&mut self, variables: I) {
+        self.add_sos(SOSType::Type2, variables)
+    }
+}
+
+/// Cardinality constraints are reformulated with binary indicator variables
+impl CardinalityConstraintSolver for LpSolveProblem {
+    fn add_cardinality_constraint(&mut self, vars: &[Variable], rhs: usize) -> ConstraintReference {
+        let vars: Vec<_> = vars
+            .iter()
+            .map(|&var| (var, self.column_bounds[var.index()], self.add_binary()))
+            .collect();
+        add_cardinality_with_indicators(self, &vars, rhs)
+    }
+}
+
+impl LpSolveProblem {
+    /// Adds a binary variable that is not in the objective
+    fn add_binary(&mut self) -> Variable {
+        let empty_column = vec![0.; self.problem.num_rows() as usize + 1];
+        assert!(self.problem.add_column(&empty_column));
+        let var = Variable::at(self.column_bounds.len());
+        assert!(self.problem.set_integer(col_num(var), true));
+        assert!(self.problem.set_bounds(col_num(var), 0., 1.));
+        self.column_bounds.push((0., 1.));
+        var
+    }
+
+    fn add_sos<I: IntoAffineExpression>(&mut self, sos_type: SOSType, variables: I) {
         let iter = variables.linear_coefficients().into_iter();
         let (len, _) = iter.size_hint();
         let mut weights = Vec::with_capacity(len);
         let mut variables = Vec::with_capacity(len);
         for (var, weight) in iter {
             weights.push(weight);
-            variables.push(var.index().try_into().expect("too many vars"));
+            variables.push(col_num(var));
         }
+        // lp_solve ignores the SOS constraints on columns that are not in any row,
+        // so the columns are put in a free row. It is not a constraint of the problem:
+        // the constraint references keep the index of the constraints in `rows`.
+        let mut coeffs = vec![0.; self.problem.num_cols() as usize + 1];
+        for &col in &variables {
+            coeffs[col as usize] = 1.;
+        }
+        let infinity = self.problem.get_infinite();
+        assert!(self
+            .problem
+            .add_constraint(&coeffs, infinity, ConstraintType::Le));
         let name = CString::new("sos").unwrap();
-        self.0
-            .add_sos_constraint(&name, SOSType::Type1, 1, &weights, &variables);
+        self.problem
+            .add_sos_constraint(&name, sos_type, 1, &weights, &variables);
     }
 }
 
 /// A coin-cbc problem solution
 pub struct LpSolveSolution {
     problem: Problem,
+    rows: Vec<Option<c_int>>,
     solution: Vec<f64>,
+    status: SolutionStatus,
+    objective: Expression,
+    direction: ObjectiveDirection,
 }
 
 impl LpSolveSolution {
@@ -152,10 +420,243 @@ impl LpSolveSolution {
     pub fn into_inner(self) -> Problem {
         self.problem
     }
+
+    /// The dual values of the rows, followed by the reduced costs of the columns,
+    /// for the minimised objective.
+    /// lp_solve only computes them for a problem that it did not solve by branch and bound.
+    #[allow(unsafe_code)]
+    fn minimised_duals(&self) -> Option<&[f64]> {
+        let lprec = self.problem.to_lprec();
+        let mut duals: *mut f64 = null_mut();
+        // SAFETY: the lprec pointer is valid while the problem lives
+        let available = unsafe {
+            lpsolve_sys::get_ptr_sensitivity_rhs(lprec, &mut duals, null_mut(), null_mut())
+        };
+        if available != 1 {
+            return None;
+        }
+        let len = (self.problem.num_rows() + self.problem.num_cols()) as usize;
+        // SAFETY: lp_solve keeps an entry per row and column until the problem changes,
+        // and the problem cannot change while the solution is borrowed
+        Some(unsafe { std::slice::from_raw_parts(duals, len) })
+    }
+
+    /// The objective given to lp_solve is negated for maximisation
+    fn objective_sign(&self) -> f64 {
+        match self.direction {
+            ObjectiveDirection::Minimisation => 1.,
+            ObjectiveDirection::Maximisation => -1.,
+        }
+    }
 }
 
 impl Solution for LpSolveSolution {
     fn value(&self, variable: Variable) -> f64 {
         self.solution[variable.index()]
     }
+
+    fn status(&self) -> SolutionStatus {
+        self.status
+    }
+}
+
+impl SolutionInfo for LpSolveSolution {
+    fn objective_value(&self) -> f64 {
+        self.eval(&self.objective)
+    }
+
+    fn best_bound(&self) -> f64 {
+        default_best_bound(self.objective_value(), self.status, self.direction)
+    }
+}
+
+/// lp_solve does not keep the reduced costs of a problem with integer variables
+/// that it solved by branch and bound: they are then NaN.
+impl SolutionWithReducedCosts for LpSolveSolution {
+    fn reduced_cost(&self, variable: Variable) -> f64 {
+        let rows = self.problem.num_rows() as usize;
+        self.minimised_duals().map_or(f64::NAN, |duals| {
+            self.objective_sign() * duals[rows + variable.index()]
+        })
+    }
+}
+
+/// lp_solve does not keep the dual values of a problem with integer variables
+/// that it solved by branch and bound: they are then NaN.
+/// The dual value of a removed constraint is zero.
+impl<'a> DualValues for &'a LpSolveSolution {
+    fn dual(&self, constraint: ConstraintReference) -> f64 {
+        match self.rows[constraint.index] {
+            // lp_solve rows are numbered from 1
+            Some(row) => self.minimised_duals().map_or(f64::NAN, |duals| {
+                self.objective_sign() * duals[row as usize - 1]
+            }),
+            None => 0.,
+        }
+    }
+}
+
+impl<'a> SolutionWithDual<'a> for LpSolveSolution {
+    type Dual = &'a LpSolveSolution;
+
+    fn compute_dual(&'a mut self) -> &'a LpSolveSolution {
+        self
+    }
+}
+
+/// The sensitivity report is made from the sensitivity analysis that lp_solve
+/// computes on the optimal basis.
+/// lp_solve does not compute it for a problem with integer variables
+/// that it solved by branch and bound: the ranges are then NaN.
+impl SolutionWithSensitivity for LpSolveSolution {
+    #[allow(unsafe_code)]
+    fn sensitivity_report(&self) -> SensitivityReport {
+        let lprec = self.problem.to_lprec();
+        let n_rows = self.problem.num_rows() as usize;
+        let n_cols = self.problem.num_cols() as usize;
+        let (mut objective_from, mut objective_till) = (null_mut(), null_mut());
+        let (mut bound_from, mut bound_till) = (null_mut(), null_mut());
+        let mut activities = null_mut();
+        let mut basis: Vec<c_int> = vec![0; 1 + n_rows];
+        // SAFETY: the lprec pointer is valid while the problem lives,
+        // and lp_solve writes the index of a basic variable for each row after the first entry
+        let available = unsafe {
+            lpsolve_sys::get_ptr_sensitivity_obj(lprec, &mut objective_from, &mut objective_till)
+                == 1
+                && lpsolve_sys::get_ptr_sensitivity_rhs(
+                    lprec,
+                    null_mut(),
+                    &mut bound_from,
+                    &mut bound_till,
+                ) == 1
+                && lpsolve_sys::get_ptr_constraints(lprec, &mut activities) == 1
+                && lpsolve_sys::get_basis(lprec, basis.as_mut_ptr(), 0) == 1
+        };
+        if !available {
+            let unknown = SensitivityRange {
+                allowable_decrease: f64::NAN,
+                allowable_increase: f64::NAN,
+            };
+            return SensitivityReport::new(vec![unknown; n_cols], vec![unknown; self.rows.len()]);
+        }
+        // SAFETY: lp_solve keeps an entry per column, or per row followed by the columns,
+        // until the problem changes, and the problem cannot change while the solution is borrowed
+        let (objective_from, objective_till, bound_from, bound_till, activities) = unsafe {
+            (
+                std::slice::from_raw_parts(objective_from, n_cols),
+                std::slice::from_raw_parts(objective_till, n_cols),
+                std::slice::from_raw_parts(bound_from, n_rows),
+                std::slice::from_raw_parts(bound_till, n_rows),
+                std::slice::from_raw_parts(activities, n_rows),
+            )
+        };
+        let infinity = self.problem.get_infinite();
+        let from_lp_solve = |value: f64| {
+            if value.abs() >= infinity {
+                value.signum() * f64::INFINITY
+            } else {
+                value
+            }
+        };
+        let sign = self.objective_sign();
+        let objective = (0..n_cols)
+            .map(|col| {
+                // The limits are the ones of the minimised objective
+                let coefficient = sign
+                    * self
+                        .objective
+                        .linear
+                        .coefficients
+                        .get(&Variable::at(col))
+                        .unwrap_or(&0.);
+                let decrease = coefficient - from_lp_solve(objective_from[col]);
+                let increase = from_lp_solve(objective_till[col]) - coefficient;
+                match self.direction {
+                    ObjectiveDirection::Minimisation => SensitivityRange {
+                        allowable_decrease: decrease,
+                        allowable_increase: increase,
+                    },
+                    ObjectiveDirection::Maximisation => SensitivityRange {
+                        allowable_decrease: increase,
+                        allowable_increase: decrease,
+                    },
+                }
+            })
+            .collect();
+        // The basic variables are numbered like the rows, followed by the columns
+        let mut basic = vec![false; 1 + n_rows + n_cols];
+        for &var in &basis[1..] {
+            basic[var.unsigned_abs() as usize] = true;
+        }
+        let constraints = self
+            .rows
+            .iter()
+            .map(|&row| {
+                let row = match row {
+                    Some(row) => row,
+                    // A removed constraint can have any bound
+                    None => {
+                        return SensitivityRange {
+                            allowable_decrease: f64::INFINITY,
+                            allowable_increase: f64::INFINITY,
+                        }
+                    }
+                };
+                // SAFETY: the row exists in the problem
+                let (lower, upper) = unsafe {
+                    (
+                        lpsolve_sys::get_rh_lower(lprec, row),
+                        lpsolve_sys::get_rh_upper(lprec, row),
+                    )
+                };
+                let (lower, upper) = (from_lp_solve(lower), from_lp_solve(upper));
+                let index = row as usize - 1;
+                let activity = activities[index];
+                if basic[row as usize] {
+                    // lp_solve gives infinite limits to basic rows, but their dual value
+                    // only stays zero until the bound reaches the value of the row
+                    SensitivityRange {
+                        allowable_decrease: upper - activity,
+                        allowable_increase: f64::INFINITY,
+                    }
+                } else {
+                    // A nonbasic row is at the bound that is the closest to its value
+                    let bound = if activity - lower < upper - activity {
+                        lower
+                    } else {
+                        upper
+                    };
+                    SensitivityRange {
+                        allowable_decrease: bound - from_lp_solve(bound_from[index]),
+                        allowable_increase: from_lp_solve(bound_till[index]) - bound,
+                    }
+                }
+            })
+            .collect();
+        SensitivityReport::new(objective, constraints)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{variable, variables, ModelWithSOS1, Solution, SolverModel};
+
+    use super::lp_solve;
+
+    #[test]
+    fn sos_on_the_last_columns() {
+        // lp_solve numbers the columns from 1: the set must not be applied to x and y
+        let mut vars = variables!();
+        let x = vars.add(variable().integer().clamp(0, 1));
+        let y = vars.add(variable().integer().clamp(0, 1));
+        let z = vars.add(variable().integer().clamp(0, 1));
+        let solution = vars
+            .maximise(3 * x + 2 * y + z)
+            .using(lp_solve)
+            .with_sos1(y + 2 * z)
+            .solve()
+            .unwrap();
+        let values = (solution.value(x), solution.value(y), solution.value(z));
+        assert_eq!(values, (1., 1., 0.));
+    }
 }
@@ -1,21 +1,40 @@
 //! A solver that uses [minilp](https://docs.rs/minilp), a pure rust solver.
+//!
+//! minilp only solves continuous problems.
+//! Integer variables are handled by a branch and bound algorithm implemented on top of it.
 
-use std::panic::catch_unwind;
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
 
-use minilp::Error;
+use minilp::{ComparisonOp, Error};
 
+use crate::cardinality_constraint_solver_trait::add_cardinality_with_indicators;
 use crate::variable::{UnsolvedProblem, VariableDefinition};
 use crate::{
     constraint::ConstraintReference,
-    solvers::{ObjectiveDirection, ResolutionError, Solution, SolverModel},
+    solvers::{
+        DualValues, MipGapError, ModifiableModel, ObjectiveDirection, ResolutionError, Solution,
+        SolutionInfo, SolutionStatus, SolutionWithDual, SolverModel, WithInitialSolution,
+        WithMipGap, WithTimeLimit, UNSUPPORTED_QUADRATIC_OBJECTIVE,
+    },
 };
-use crate::{Constraint, Variable};
+use crate::{
+    CardinalityConstraintSolver, Constraint, SensitivityRange, SensitivityReport,
+    SolutionWithSensitivity, Variable,
+};
+
+/// A value is considered integer if it is this close to an integer
+const INTEGRALITY_TOLERANCE: f64 = 1e-6;
+
+/// A constraint or a bound is considered tight if the value is this close to it, relatively
+const TIGHTNESS_TOLERANCE: f64 = 1e-6;
 
 /// The [minilp](https://docs.rs/minilp) solver,
 /// to be used with [UnsolvedProblem::using].
 pub fn minilp(to_solve: UnsolvedProblem) -> MiniLpProblem {
     let UnsolvedProblem {
         objective,
+        quadratic_objective,
         direction,
         variables,
     } = to_solve;
@@ -23,7 +42,8 @@ pub fn minilp(to_solve: UnsolvedProblem) -> MiniLpProblem {
         ObjectiveDirection::Maximisation => minilp::OptimizationDirection::Maximize,
         ObjectiveDirection::Minimisation => minilp::OptimizationDirection::Minimize,
     });
-    let mut integers: Vec<minilp::Variable> = vec![];
+    let mut integers: Vec<IntegerVariable> = vec![];
+    let mut columns = Vec::with_capacity(variables.len());
     let variables: Vec<minilp::Variable> = variables
         .iter_variables_with_def()
         .map(
@@ -37,9 +57,10 @@ pub fn minilp(to_solve: UnsolvedProblem) -> MiniLpProblem {
                 },
             )| {
                 let coeff = *objective.linear.coefficients.get(&var).unwrap_or(&0.);
+                columns.push((coeff, (min, max)));
                 let var = problem.add_var(coeff, (min, max));
                 if is_integer {
-                    integers.push(var);
+                    integers.push(IntegerVariable { var, min, max });
                 }
                 var
             },
@@ -47,58 +68,166 @@ pub fn minilp(to_solve: UnsolvedProblem) -> MiniLpProblem {
         .collect();
     MiniLpProblem {
         problem,
+        direction,
+        objective_constant: objective.constant,
+        has_quadratic_objective: !quadratic_objective.is_empty(),
         variables,
         integers,
-        n_constraints: 0,
+        columns,
+        constraints: vec![],
+        modified: false,
+        initial_solution: HashMap::new(),
+        mip_gap: None,
+        node_limit: None,
+        time_limit: None,
     }
 }
 
 /// A minilp model
 pub struct MiniLpProblem {
     problem: minilp::Problem,
+    direction: ObjectiveDirection,
+    objective_constant: f64,
+    // Solving fails if the objective is quadratic
+    has_quadratic_objective: bool,
     variables: Vec<minilp::Variable>,
-    integers: Vec<minilp::Variable>,
-    n_constraints: usize,
+    integers: Vec<IntegerVariable>,
+    // The objective coefficient and the bounds of each variable,
+    // and the constraints, to rebuild the problem after a modification
+    columns: Vec<(f64, (f64, f64))>,
+    constraints: Vec<Option<Constraint>>,
+    modified: bool,
+    // The initial values of the variables, by index
+    initial_solution: HashMap<usize, f64>,
+    mip_gap: Option<f32>,
+    node_limit: Option<usize>,
+    time_limit: Option<f64>,
+}
+
+#[derive(Clone, Copy)]
+struct IntegerVariable {
+    var: minilp::Variable,
+    min: f64,
+    max: f64,
 }
 
 impl MiniLpProblem {
-    /// Get the inner minilp model
+    /// Get the inner minilp model.
+    /// It does not reflect the [modifications](ModifiableModel) made since the last resolution.
     pub fn as_inner(&self) -> &minilp::Problem {
         &self.problem
     }
+
+    /// Sets the maximum number of linear relaxations solved by the branch and bound algorithm
+    /// used when the problem contains integer variables.
+    /// When the limit is reached, the best integer solution found so far is returned.
+    pub fn set_node_limit(mut self, node_limit: usize) -> MiniLpProblem {
+        self.node_limit = Some(node_limit);
+        self
+    }
 }
 
-impl SolverModel for MiniLpProblem {
-    type Solution = MiniLpSolution;
-    type Error = ResolutionError;
+impl MiniLpProblem {
+    /// Recreates the minilp problem from the current variables and constraints
+    fn rebuild(&mut self) {
+        let mut problem = minilp::Problem::new(match self.direction {
+            ObjectiveDirection::Maximisation => minilp::OptimizationDirection::Maximize,
+            ObjectiveDirection::Minimisation => minilp::OptimizationDirection::Minimize,
+        });
+        for &(coeff, bounds) in &self.columns {
+            problem.add_var(coeff, bounds);
+        }
+        for constraint in self.constraints.iter().flatten() {
+            add_to_problem(&mut problem, &self.variables, constraint);
+        }
+        self.problem = problem;
+        self.modified = false;
+    }
 
-    fn solve(self) -> Result<Self::Solution, Self::Error> {
-        let mut solution = self.problem.solve()?;
-        for int_var in self.integers {
-            solution = catch_unwind(|| solution.add_gomory_cut(int_var)).map_err(|_| {
-                ResolutionError::Other("minilp does not support integer variables")
-            })??;
+    fn solve_model(&mut self) -> Result<MiniLpSolution, ResolutionError> {
+        if self.has_quadratic_objective {
+            return Err(UNSUPPORTED_QUADRATIC_OBJECTIVE);
+        }
+        if self.modified {
+            self.rebuild();
+        }
+        // Time limits too large to be represented are ignored
+        let deadline = self
+            .time_limit
+            .and_then(|seconds| Duration::try_from_secs_f64(seconds.max(0.)).ok())
+            .and_then(|duration| Instant::now().checked_add(duration));
+        let relaxation = self.problem.solve()?;
+        let (solution, status, best_bound) = if self.integers.is_empty() {
+            let objective = relaxation.objective();
+            (relaxation, SolutionStatus::Optimal, objective)
+        } else {
+            let start = self.start_solution(&relaxation)?;
+            BranchAndBound {
+                integers: &self.integers,
+                direction: self.direction,
+                mip_gap: self.mip_gap.map_or(0., f64::from),
+                node_limit: self.node_limit.unwrap_or(usize::MAX),
+                deadline,
+            }
+            .solve(relaxation, start)?
+        };
+        let mut is_integer = vec![false; self.variables.len()];
+        for int_var in &self.integers {
+            is_integer[int_var.var.idx()] = true;
         }
         Ok(MiniLpSolution {
             solution,
-            variables: self.variables,
+            variables: self.variables.clone(),
+            is_integer,
+            direction: self.direction,
+            columns: self.columns.clone(),
+            constraints: self.constraints.clone(),
+            status,
+            objective_constant: self.objective_constant,
+            best_bound: best_bound + self.objective_constant,
         })
     }
+}
+
+impl MiniLpProblem {
+    /// The relaxation with the integer variables that have an initial value fixed to it
+    fn start_solution(
+        &self,
+        relaxation: &minilp::Solution,
+    ) -> Result<Option<minilp::Solution>, ResolutionError> {
+        if self.initial_solution.is_empty() {
+            return Ok(None);
+        }
+        let mut solution = relaxation.clone();
+        for int_var in &self.integers {
+            if let Some(value) = self.initial_solution.get(&int_var.var.idx()) {
+                solution = match solution.fix_var(int_var.var, value.round()) {
+                    Ok(solution) => solution,
+                    Err(Error::Infeasible) => return Ok(None),
+                    Err(e) => return Err(e.into()),
+                };
+            }
+        }
+        Ok(Some(solution))
+    }
+}
+
+impl SolverModel for MiniLpProblem {
+    type Solution = MiniLpSolution;
+    type Error = ResolutionError;
+
+    fn solve(mut self) -> Result<Self::Solution, Self::Error> {
+        self.solve_model()
+    }
 
     fn add_constraint(&mut self, constraint: Constraint) -> ConstraintReference {
-        let index = self.n_constraints;
-        let op = match constraint.is_equality {
-            true => minilp::ComparisonOp::Eq,
-            false => minilp::ComparisonOp::Le,
-        };
-        let constant = -constraint.expression.constant;
-        let mut linear_expr = minilp::LinearExpr::empty();
-        for (var, coefficient) in constraint.expression.linear.coefficients {
-            linear_expr.add(self.variables[var.index()], coefficient);
+        let index = self.constraints.len();
+        if !self.modified {
+            add_to_problem(&mut self.problem, &self.variables, &constraint);
         }
-        self.problem.add_constraint(linear_expr, op, constant);
-        self.n_constraints += 1;
-        ConstraintReference { index }
+        let name = constraint.name.clone();
+        self.constraints.push(Some(constraint));
+        ConstraintReference::new(index, name)
     }
 
     fn name() -> &'static str {
@@ -106,6 +235,318 @@ impl SolverModel for MiniLpProblem {
     }
 }
 
+fn add_to_problem(
+    problem: &mut minilp::Problem,
+    variables: &[minilp::Variable],
+    constraint: &Constraint,
+) {
+    let op = match constraint.is_equality {
+        true => minilp::ComparisonOp::Eq,
+        false => minilp::ComparisonOp::Le,
+    };
+    let (lower, upper) = constraint.bounds();
+    let mut linear_expr = minilp::LinearExpr::empty();
+    for (&var, &coefficient) in &constraint.expression.linear.coefficients {
+        linear_expr.add(variables[var.index()], coefficient);
+    }
+    // minilp doesn't support ranges: the lower bound is added as a separate constraint
+    if constraint.is_ranged() {
+        problem.add_constraint(linear_expr.clone(), ComparisonOp::Ge, lower);
+    }
+    problem.add_constraint(linear_expr, op, upper);
+}
+
+/// minilp problems cannot be modified in place:
+/// the problem is rebuilt before solving it again.
+impl ModifiableModel for MiniLpProblem {
+    fn set_objective_coefficient(&mut self, variable: Variable, coefficient: f64) {
+        self.columns[variable.index()].0 = coefficient;
+        self.modified = true;
+    }
+
+    fn set_variable_bounds(&mut self, variable: Variable, min: f64, max: f64) {
+        self.columns[variable.index()].1 = (min, max);
+        for int_var in &mut self.integers {
+            if int_var.var.idx() == variable.index() {
+                int_var.min = min;
+                int_var.max = max;
+            }
+        }
+        self.modified = true;
+    }
+
+    fn set_constraint_bounds(&mut self, constraint: &ConstraintReference, lower: f64, upper: f64) {
+        if let Some(c) = &mut self.constraints[constraint.index] {
+            let mut linear = std::mem::take(&mut c.expression);
+            linear.constant = 0.;
+            *c = Constraint {
+                name: c.name.take(),
+                ..crate::constraint::range(lower, linear, upper)
+            };
+            self.modified = true;
+        }
+    }
+
+    fn remove_constraint(&mut self, constraint: &ConstraintReference) {
+        self.constraints[constraint.index] = None;
+        self.modified = true;
+    }
+
+    fn resolve(&mut self) -> Result<MiniLpSolution, ResolutionError> {
+        self.solve_model()
+    }
+}
+
+/// Cardinality constraints are reformulated with binary indicator variables
+impl CardinalityConstraintSolver for MiniLpProblem {
+    fn add_cardinality_constraint(&mut self, vars: &[Variable], rhs: usize) -> ConstraintReference {
+        let vars: Vec<_> = vars
+            .iter()
+            .map(|&var| (var, self.columns[var.index()].1, self.add_binary()))
+            .collect();
+        add_cardinality_with_indicators(self, &vars, rhs)
+    }
+}
+
+impl MiniLpProblem {
+    /// Adds a binary variable that is not in the objective
+    fn add_binary(&mut self) -> Variable {
+        if self.modified {
+            self.rebuild();
+        }
+        let var = self.problem.add_var(0., (0., 1.));
+        self.variables.push(var);
+        self.columns.push((0., (0., 1.)));
+        self.integers.push(IntegerVariable {
+            var,
+            min: 0.,
+            max: 1.,
+        });
+        Variable::at(var.idx())
+    }
+}
+
+/// The integer variables that were given a value are fixed to it, and the linear relaxation
+/// gives the values of the other variables. If this is an integer solution,
+/// the branch and bound algorithm starts with it as its best known solution.
+/// The initial values of continuous variables are not used.
+impl WithInitialSolution for MiniLpProblem {
+    fn set_initial_solution<I: IntoIterator<Item = (Variable, f64)>>(&mut self, solution: I) {
+        self.initial_solution.extend(
+            solution
+                .into_iter()
+                .map(|(var, value)| (var.index(), value)),
+        );
+    }
+}
+
+/// The time limit only applies to the branch and bound algorithm:
+/// minilp itself cannot be interrupted while solving a linear relaxation.
+impl WithTimeLimit for MiniLpProblem {
+    fn time_limit(&self) -> Option<f64> {
+        self.time_limit
+    }
+
+    fn with_time_limit(mut self, seconds: f64) -> Self {
+        self.time_limit = Some(seconds);
+        self
+    }
+}
+
+impl WithMipGap for MiniLpProblem {
+    fn mip_gap(&self) -> Option<f32> {
+        self.mip_gap
+    }
+
+    fn with_mip_gap(mut self, mip_gap: f32) -> Result<Self, MipGapError> {
+        if mip_gap.is_sign_negative() {
+            Err(MipGapError::Negative)
+        } else if mip_gap.is_infinite() {
+            Err(MipGapError::Infinite)
+        } else {
+            self.mip_gap = Some(mip_gap);
+            Ok(self)
+        }
+    }
+}
+
+/// A node of the branch and bound tree: the relaxation of the problem
+/// with tightened bounds on the integer variables
+struct Node {
+    solution: minilp::Solution,
+    /// The bounds of the integer variables, in the same order as [BranchAndBound::integers]
+    bounds: Vec<(f64, f64)>,
+}
+
+struct BranchAndBound<'a> {
+    integers: &'a [IntegerVariable],
+    direction: ObjectiveDirection,
+    /// Relative gap between the incumbent and the best bound at which the search stops
+    mip_gap: f64,
+    node_limit: usize,
+    deadline: Option<Instant>,
+}
+
+impl BranchAndBound<'_> {
+    /// The objective of a solution, expressed so that lower is always better
+    fn key(&self, solution: &minilp::Solution) -> f64 {
+        match self.direction {
+            ObjectiveDirection::Minimisation => solution.objective(),
+            ObjectiveDirection::Maximisation => -solution.objective(),
+        }
+    }
+
+    /// Whether a node with the given bound cannot contain a solution
+    /// significantly better than the incumbent
+    fn can_prune(&self, bound: f64, incumbent: &Option<(f64, minilp::Solution)>) -> bool {
+        incumbent.as_ref().is_some_and(|&(best, _)| {
+            let tolerance = 1e-9 * (1. + best.abs()) + self.mip_gap * best.abs();
+            bound >= best - tolerance
+        })
+    }
+
+    /// The most fractional integer variable, with its position in `integers` and its value
+    fn branching_variable(&self, solution: &minilp::Solution) -> Option<(usize, f64)> {
+        self.integers
+            .iter()
+            .enumerate()
+            .map(|(i, int_var)| (i, solution[int_var.var]))
+            .map(|(i, value)| (i, value, (value - value.floor()).min(value.ceil() - value)))
+            .filter(|&(_, _, fractionality)| fractionality > INTEGRALITY_TOLERANCE)
+            .max_by(|a, b| a.2.total_cmp(&b.2))
+            .map(|(i, value, _)| (i, value))
+    }
+
+    /// Explores the tree depth first until a first integer solution is found,
+    /// or from the start if an integer starting solution is given,
+    /// then always expands the open node with the best bound.
+    /// Returns the best integer solution, its status, and the best bound on the objective.
+    fn solve(
+        &self,
+        relaxation: minilp::Solution,
+        start: Option<minilp::Solution>,
+    ) -> Result<(minilp::Solution, SolutionStatus, f64), ResolutionError> {
+        let root = Node {
+            solution: relaxation,
+            bounds: self.integers.iter().map(|v| (v.min, v.max)).collect(),
+        };
+        let mut open = vec![root];
+        let mut incumbent: Option<(f64, minilp::Solution)> = start
+            .filter(|solution| self.branching_variable(solution).is_none())
+            .map(|solution| (self.key(&solution), solution));
+        let mut explored = 0;
+        let mut status = SolutionStatus::Optimal;
+        while !open.is_empty() {
+            if explored >= self.node_limit {
+                status = SolutionStatus::Feasible;
+                break;
+            }
+            if self
+                .deadline
+                .is_some_and(|deadline| Instant::now() >= deadline)
+            {
+                status = SolutionStatus::TimeLimit;
+                break;
+            }
+            explored += 1;
+            let node = if incumbent.is_none() {
+                open.pop().expect("open is not empty")
+            } else {
+                let (best, _) = open
+                    .iter()
+                    .enumerate()
+                    .map(|(i, node)| (i, self.key(&node.solution)))
+                    .min_by(|a, b| a.1.total_cmp(&b.1))
+                    .expect("open is not empty");
+                open.swap_remove(best)
+            };
+            let bound = self.key(&node.solution);
+            if self.can_prune(bound, &incumbent) {
+                // Nodes are selected by best bound, so all the remaining nodes can be pruned
+                let best = incumbent.as_ref().map_or(bound, |&(best, _)| best);
+                if bound < best - 1e-9 * (1. + best.abs()) {
+                    status = SolutionStatus::GapLimit;
+                }
+                open.push(node);
+                break;
+            }
+            let (i, value) = match self.branching_variable(&node.solution) {
+                Some(branch) => branch,
+                None => {
+                    incumbent = Some((bound, node.solution));
+                    continue;
+                }
+            };
+            let (min, max) = node.bounds[i];
+            let down = (min, value.floor());
+            let up = (value.ceil(), max);
+            // Explore the closest integer first
+            let children = if value - value.floor() < 0.5 {
+                [up, down]
+            } else {
+                [down, up]
+            };
+            for (min, max) in children {
+                if min > max {
+                    continue;
+                }
+                if let Some(solution) = self.child(&node, i, min, max)? {
+                    if !self.can_prune(self.key(&solution), &incumbent) {
+                        let mut bounds = node.bounds.clone();
+                        bounds[i] = (min, max);
+                        open.push(Node { solution, bounds });
+                    }
+                }
+            }
+        }
+        match incumbent {
+            Some((best, solution)) => {
+                let bound = open
+                    .iter()
+                    .map(|node| self.key(&node.solution))
+                    .fold(best, f64::min);
+                let bound = match self.direction {
+                    ObjectiveDirection::Minimisation => bound,
+                    ObjectiveDirection::Maximisation => -bound,
+                };
+                Ok((solution, status, bound))
+            }
+            None if open.is_empty() => Err(ResolutionError::Infeasible),
+            None if status == SolutionStatus::TimeLimit => Err(ResolutionError::Other(
+                "the time limit was reached before an integer solution was found",
+            )),
+            None => Err(ResolutionError::Other(
+                "the node limit was reached before an integer solution was found",
+            )),
+        }
+    }
+
+    /// Solves the relaxation of the node with new bounds on the integer variable number `i`.
+    /// Returns None if it is infeasible
+    fn child(
+        &self,
+        node: &Node,
+        i: usize,
+        min: f64,
+        max: f64,
+    ) -> Result<Option<minilp::Solution>, ResolutionError> {
+        let var = self.integers[i].var;
+        let solution = node.solution.clone();
+        let result = if min == max {
+            solution.fix_var(var, min)
+        } else if min > node.bounds[i].0 {
+            solution.add_constraint([(var, 1.)], ComparisonOp::Ge, min)
+        } else {
+            solution.add_constraint([(var, 1.)], ComparisonOp::Le, max)
+        };
+        match result {
+            Ok(solution) => Ok(Some(solution)),
+            Err(Error::Infeasible) => Ok(None),
+            Err(e) => Err(e.into()),
+        }
+    }
+}
+
 impl From<minilp::Error> for ResolutionError {
     fn from(minilp_error: Error) -> Self {
         match minilp_error {
@@ -119,6 +560,14 @@ impl From<minilp::Error> for ResolutionError {
 pub struct MiniLpSolution {
     solution: minilp::Solution,
     variables: Vec<minilp::Variable>,
+    is_integer: Vec<bool>,
+    // The problem that was solved, to compute the dual values
+    direction: ObjectiveDirection,
+    columns: Vec<(f64, (f64, f64))>,
+    constraints: Vec<Option<Constraint>>,
+    status: SolutionStatus,
+    objective_constant: f64,
+    best_bound: f64,
 }
 
 impl MiniLpSolution {
@@ -128,15 +577,326 @@ impl MiniLpSolution {
     }
 }
 
+/// Whether a value is equal to a finite bound
+fn is_tight(value: f64, bound: f64) -> bool {
+    bound.is_finite() && (value - bound).abs() <= TIGHTNESS_TOLERANCE * (1. + bound.abs())
+}
+
+/// The problem whose solutions are the dual solutions that satisfy
+/// the complementary slackness conditions with a primal solution
+struct DualProblem {
+    problem: minilp::Problem,
+    /// The dual value of each constraint, if it can be nonzero
+    duals: Vec<Option<minilp::Variable>>,
+    reduced_costs: Vec<minilp::Variable>,
+    /// The objective coefficient of the variable whose cost varies, if any
+    cost: Option<minilp::Variable>,
+}
+
+impl MiniLpSolution {
+    fn raw_value(&self, var: Variable) -> f64 {
+        self.solution[self.variables[var.index()]]
+    }
+
+    fn activity(&self, constraint: &Constraint) -> f64 {
+        let coefficients = &constraint.expression.linear.coefficients;
+        coefficients
+            .iter()
+            .map(|(&var, &coefficient)| coefficient * self.raw_value(var))
+            .sum()
+    }
+
+    /// The possible values of the dual value of a constraint or the reduced cost of a variable,
+    /// depending on the bounds it is equal to
+    fn dual_bounds(&self, at_lower: bool, at_upper: bool) -> (f64, f64) {
+        let is_positive = match self.direction {
+            ObjectiveDirection::Minimisation => at_lower,
+            ObjectiveDirection::Maximisation => at_upper,
+        };
+        match (at_lower, at_upper) {
+            (true, true) => (f64::NEG_INFINITY, f64::INFINITY),
+            (false, false) => (0., 0.),
+            _ if is_positive => (0., f64::INFINITY),
+            _ => (f64::NEG_INFINITY, 0.),
+        }
+    }
+
+    /// Without a varying cost, the objective of the dual problem is to find the dual solution
+    /// with the lowest reduced costs. With one, the objective coefficient of the given variable
+    /// becomes a variable of the dual problem, which is minimised or maximised.
+    fn dual_problem(
+        &self,
+        varying_cost: Option<(usize, minilp::OptimizationDirection)>,
+    ) -> DualProblem {
+        let direction = varying_cost.map_or(minilp::OptimizationDirection::Minimize, |(_, d)| d);
+        let mut problem = minilp::Problem::new(direction);
+        // For each variable, its objective coefficient is the sum of the dual values
+        // of its constraints, weighted by its coefficients in them, and its reduced cost
+        let mut columns = vec![minilp::LinearExpr::empty(); self.columns.len()];
+        let mut duals = Vec::with_capacity(self.constraints.len());
+        for constraint in &self.constraints {
+            let dual = constraint.as_ref().and_then(|c| {
+                let activity = self.activity(c);
+                let (lower, upper) = c.bounds();
+                let bounds = self.dual_bounds(is_tight(activity, lower), is_tight(activity, upper));
+                if bounds == (0., 0.) {
+                    return None;
+                }
+                let dual = problem.add_var(0., bounds);
+                for (&var, &coefficient) in &c.expression.linear.coefficients {
+                    columns[var.index()].add(dual, coefficient);
+                }
+                Some(dual)
+            });
+            duals.push(dual);
+        }
+        let mut reduced_costs = Vec::with_capacity(columns.len());
+        let mut varying = None;
+        for (i, (&(cost, (min, max)), mut column)) in self.columns.iter().zip(columns).enumerate() {
+            let value = self.solution[self.variables[i]];
+            // Integer variables are considered fixed to their value
+            let bounds = if self.is_integer[i] {
+                (f64::NEG_INFINITY, f64::INFINITY)
+            } else {
+                self.dual_bounds(is_tight(value, min), is_tight(value, max))
+            };
+            let weight = match (varying_cost, bounds) {
+                (Some(_), _) => 0.,
+                (None, (0., _)) => 1.,
+                (None, (_, 0.)) => -1.,
+                (None, _) => 0.,
+            };
+            let reduced_cost = problem.add_var(weight, bounds);
+            column.add(reduced_cost, 1.);
+            reduced_costs.push(reduced_cost);
+            match varying_cost {
+                Some((index, _)) if index == i => {
+                    let cost = problem.add_var(1., (f64::NEG_INFINITY, f64::INFINITY));
+                    column.add(cost, -1.);
+                    problem.add_constraint(column, ComparisonOp::Eq, 0.);
+                    varying = Some(cost);
+                }
+                _ => problem.add_constraint(column, ComparisonOp::Eq, cost),
+            }
+        }
+        DualProblem {
+            problem,
+            duals,
+            reduced_costs,
+            cost: varying,
+        }
+    }
+
+    /// Finds dual values and reduced costs that satisfy the complementary slackness conditions
+    /// with the solution
+    fn dual_solution(&self) -> Result<(Vec<f64>, Vec<f64>), Error> {
+        let dual_problem = self.dual_problem(None);
+        let solution = dual_problem.problem.solve()?;
+        let duals = dual_problem.duals.iter();
+        let reduced_costs = dual_problem.reduced_costs.iter();
+        Ok((
+            duals.map(|d| d.map_or(0., |d| solution[d])).collect(),
+            reduced_costs.map(|&d| solution[d]).collect(),
+        ))
+    }
+
+    /// The range of objective coefficients of a variable for which the solution stays optimal
+    fn cost_range(&self, index: usize) -> SensitivityRange {
+        sensitivity_range(self.columns[index].0, |direction| {
+            let dual_problem = self.dual_problem(Some((index, direction)));
+            let cost = dual_problem.cost.expect("the cost varies");
+            Ok(dual_problem.problem.solve()?[cost])
+        })
+    }
+
+    /// The range of the bound of a constraint for which its dual value stays the same.
+    /// The variables and constraints that have a nonzero reduced cost or dual value
+    /// stay at the bound they are equal to.
+    fn bound_range(&self, index: usize, duals: &[f64], reduced_costs: &[f64]) -> SensitivityRange {
+        let varying = match &self.constraints[index] {
+            Some(constraint) => constraint,
+            // A removed constraint can have any bound
+            None => {
+                return SensitivityRange {
+                    allowable_decrease: f64::INFINITY,
+                    allowable_increase: f64::INFINITY,
+                }
+            }
+        };
+        let (lower, upper) = varying.bounds();
+        let varies_lower = !varying.is_equality
+            && is_tight(self.activity(varying), lower)
+            && !is_tight(self.activity(varying), upper);
+        let bound = if varies_lower { lower } else { upper };
+        sensitivity_range(bound, |direction| {
+            let mut problem = minilp::Problem::new(direction);
+            let variables: Vec<minilp::Variable> = self
+                .columns
+                .iter()
+                .enumerate()
+                .map(|(i, &(_, bounds))| {
+                    let value = self.solution[self.variables[i]];
+                    let is_fixed =
+                        self.is_integer[i] || reduced_costs[i].abs() > TIGHTNESS_TOLERANCE;
+                    problem.add_var(0., if is_fixed { (value, value) } else { bounds })
+                })
+                .collect();
+            let bound = problem.add_var(1., (f64::NEG_INFINITY, f64::INFINITY));
+            for (i, constraint) in self.constraints.iter().enumerate() {
+                let c = match constraint {
+                    Some(c) => c,
+                    None => continue,
+                };
+                let (mut lower, mut upper) = c.bounds();
+                if duals[i].abs() > TIGHTNESS_TOLERANCE {
+                    // The constraint stays equal to the bound it is equal to
+                    let activity = self.activity(c);
+                    if is_tight(activity, upper) {
+                        lower = upper;
+                    } else {
+                        upper = lower;
+                    }
+                }
+                let mut expr = minilp::LinearExpr::empty();
+                for (&var, &coefficient) in &c.expression.linear.coefficients {
+                    expr.add(variables[var.index()], coefficient);
+                }
+                if i == index {
+                    // The varying bound is moved to the left-hand side
+                    let is_fixed = lower == upper;
+                    if varies_lower || is_fixed {
+                        let mut lower_expr = expr.clone();
+                        lower_expr.add(bound, -1.);
+                        problem.add_constraint(lower_expr, ComparisonOp::Ge, 0.);
+                        lower = f64::NEG_INFINITY;
+                    }
+                    if !varies_lower || is_fixed {
+                        expr.add(bound, -1.);
+                        problem.add_constraint(expr.clone(), ComparisonOp::Le, 0.);
+                        upper = f64::INFINITY;
+                    }
+                }
+                if lower > f64::NEG_INFINITY {
+                    problem.add_constraint(expr.clone(), ComparisonOp::Ge, lower);
+                }
+                if upper < f64::INFINITY {
+                    problem.add_constraint(expr, ComparisonOp::Le, upper);
+                }
+            }
+            Ok(problem.solve()?[bound])
+        })
+    }
+}
+
+/// The range between the lowest and the highest value found by minimising and maximising
+fn sensitivity_range(
+    value: f64,
+    extreme: impl Fn(minilp::OptimizationDirection) -> Result<f64, Error>,
+) -> SensitivityRange {
+    let extreme = |direction, unbounded: f64| match extreme(direction) {
+        Ok(extreme) => extreme,
+        Err(Error::Unbounded) => unbounded,
+        // The current value should always be feasible: this can only be a numerical error
+        Err(Error::Infeasible) => f64::NAN,
+    };
+    let lowest = extreme(minilp::OptimizationDirection::Minimize, f64::NEG_INFINITY);
+    let highest = extreme(minilp::OptimizationDirection::Maximize, f64::INFINITY);
+    SensitivityRange {
+        allowable_decrease: value - lowest,
+        allowable_increase: highest - value,
+    }
+}
+
 impl Solution for MiniLpSolution {
     fn value(&self, variable: Variable) -> f64 {
-        self.solution[self.variables[variable.index()]]
+        let value = self.solution[self.variables[variable.index()]];
+        if self.is_integer[variable.index()] {
+            value.round()
+        } else {
+            value
+        }
+    }
+
+    fn status(&self) -> SolutionStatus {
+        self.status
+    }
+}
+
+impl SolutionInfo for MiniLpSolution {
+    fn objective_value(&self) -> f64 {
+        self.solution.objective() + self.objective_constant
+    }
+
+    fn best_bound(&self) -> f64 {
+        self.best_bound
+    }
+}
+
+/// The dual values of a [MiniLpSolution]
+pub struct MiniLpDualValues {
+    duals: Vec<f64>,
+}
+
+impl DualValues for MiniLpDualValues {
+    fn dual(&self, constraint: ConstraintReference) -> f64 {
+        self.duals[constraint.index]
+    }
+}
+
+/// minilp does not give the dual values of its solutions, so they are computed
+/// from the primal solution, by solving a linear problem of the size of the original one
+/// with the complementary slackness conditions.
+/// When several dual solutions exist, the one with the lowest reduced costs is returned.
+/// Integer variables are considered fixed to their value in the solution.
+/// If numerical errors prevent finding a dual solution, all the dual values are NaN.
+impl<'a> SolutionWithDual<'a> for MiniLpSolution {
+    type Dual = MiniLpDualValues;
+
+    fn compute_dual(&'a mut self) -> MiniLpDualValues {
+        let duals = self
+            .dual_solution()
+            .map(|(duals, _)| duals)
+            .unwrap_or_else(|_| vec![f64::NAN; self.constraints.len()]);
+        MiniLpDualValues { duals }
+    }
+}
+
+/// minilp does not expose its final basis, so the sensitivity of a minilp solution
+/// is not a ranging of that basis. It is computed by solving two linear problems
+/// of the size of the original one for every variable and every constraint,
+/// which is much slower than ranging.
+/// The range of an objective coefficient is the one over which the solution stays optimal.
+/// The range of a bound is the one over which the dual values stay optimal, with the variables
+/// and constraints that have a nonzero reduced cost or dual value staying at their bound.
+/// They can be larger than the ranges in which the optimal basis does not change.
+/// Ranges that cannot be computed because of numerical errors are NaN.
+impl SolutionWithSensitivity for MiniLpSolution {
+    fn sensitivity_report(&self) -> SensitivityReport {
+        let objective = (0..self.columns.len())
+            .map(|i| self.cost_range(i))
+            .collect();
+        let constraints = match self.dual_solution() {
+            Ok((duals, reduced_costs)) => (0..self.constraints.len())
+                .map(|i| self.bound_range(i, &duals, &reduced_costs))
+                .collect(),
+            Err(_) => {
+                let unknown = SensitivityRange {
+                    allowable_decrease: f64::NAN,
+                    allowable_increase: f64::NAN,
+                };
+                vec![unknown; self.constraints.len()]
+            }
+        };
+        SensitivityReport::new(objective, constraints)
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::{variable, variables, Solution, SolverModel};
+    use crate::solvers::{SolutionStatus, WithInitialSolution, WithMipGap, WithTimeLimit};
+    use crate::{
+        constraint, variable, variables, ResolutionError, Solution, SolutionInfo, SolverModel,
+    };
 
     use super::minilp;
 
@@ -153,4 +913,124 @@ mod tests {
             .unwrap();
         assert_eq!((solution.value(x), solution.value(y)), (0.5, 3.))
     }
+
+    #[test]
+    fn can_solve_integer_knapsack() {
+        let weights = [12., 2., 1., 1., 4.];
+        let values = [4., 2., 1., 2., 10.];
+        let mut vars = variables!();
+        let take = vars.add_vector(variable().binary(), weights.len());
+        let value: crate::Expression = take.iter().zip(values).map(|(&t, v)| t * v).sum();
+        let weight: crate::Expression = take.iter().zip(weights).map(|(&t, w)| t * w).sum();
+        let solution = vars
+            .maximise(value.clone())
+            .using(minilp)
+            .with(constraint!(weight <= 15))
+            .solve()
+            .unwrap();
+        let taken: Vec<f64> = take.iter().map(|&t| solution.value(t)).collect();
+        assert_eq!(taken, [0., 1., 1., 1., 1.]);
+        assert_eq!(solution.eval(value), 15.);
+        assert_eq!(solution.objective_value(), 15.);
+    }
+
+    #[test]
+    fn can_solve_general_integers() {
+        variables! {vars: x (integer); y (integer);}
+        // The continuous optimum is at x = 1.95, y = 2.85
+        let solution = vars
+            .maximise(x + y)
+            .using(minilp)
+            .with(constraint!(-2 * x + 2 * y >= 1.8))
+            .with(constraint!(-8 * x + 10 * y <= 13))
+            .with(constraint!(x >= 0))
+            .with(constraint!(y >= 0))
+            .solve()
+            .unwrap();
+        assert_eq!((solution.value(x), solution.value(y)), (1., 2.));
+    }
+
+    #[test]
+    fn integer_infeasible() {
+        variables! {vars: 0 <= x (integer) <= 10;}
+        let result = vars
+            .minimise(x)
+            .using(minilp)
+            .with(constraint!(4 * x >= 5))
+            .with(constraint!(4 * x <= 7))
+            .solve();
+        assert_eq!(result.err(), Some(ResolutionError::Infeasible));
+    }
+
+    #[test]
+    fn mip_gap_and_node_limit() {
+        let mut vars = variables!();
+        let xs = vars.add_vector(variable().integer().clamp(0, 10), 6);
+        let objective: crate::Expression = xs
+            .iter()
+            .enumerate()
+            .map(|(i, &x)| (i as f64 + 3.) * x)
+            .sum();
+        let weight: crate::Expression = xs
+            .iter()
+            .enumerate()
+            .map(|(i, &x)| (i as f64 + 2.) * x)
+            .sum();
+        let make = || {
+            vars.clone()
+                .maximise(objective.clone())
+                .using(minilp)
+                .with(constraint!(weight.clone() <= 40.5))
+        };
+        let optimal = make().solve().unwrap();
+        assert_eq!(optimal.status(), SolutionStatus::Optimal);
+        assert_eq!(optimal.gap(), 0.);
+        let optimal = optimal.eval(&objective);
+        let gap = make().with_mip_gap(0.5).unwrap().solve().unwrap();
+        assert_eq!(gap.status(), SolutionStatus::GapLimit);
+        assert!(gap.eval(&objective) >= optimal / 2.);
+        assert!(gap.best_bound() >= optimal && gap.gap() <= 0.5);
+        let limited = make().set_node_limit(1).solve();
+        assert!(matches!(limited.err(), Some(ResolutionError::Other(_))));
+        let limited = make().set_node_limit(20).solve().unwrap();
+        assert_eq!(limited.status(), SolutionStatus::Feasible);
+        assert!(limited.objective_value() <= optimal && limited.best_bound() >= optimal);
+    }
+
+    #[test]
+    fn initial_solution() {
+        variables! {vars: 0 <= x (integer) <= 10; 0 <= y <= 10;}
+        let make = || {
+            vars.clone()
+                .maximise(2 * x + y)
+                .using(minilp)
+                .with(constraint!(2 * x + 2 * y <= 7))
+                .set_node_limit(1)
+        };
+        // The relaxation is fractional, so one node is not enough to find an integer solution
+        assert!(make().solve().is_err());
+        // y is computed from the initial value of x
+        let solution = make().with_initial_solution(vec![(x, 1.)]).solve().unwrap();
+        assert_eq!(solution.status(), SolutionStatus::Feasible);
+        assert_eq!((solution.value(x), solution.value(y)), (1., 2.5));
+        // An infeasible start is ignored
+        let result = make().with_initial_solution(vec![(x, 4.)]).solve();
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn time_limit() {
+        variables! {vars: 0 <= x (integer) <= 10; 0 <= y (integer) <= 10;}
+        let make = || {
+            vars.clone()
+                .maximise(x + y)
+                .using(minilp)
+                .with(constraint!(2 * x + 2 * y <= 7))
+        };
+        let expired = make().with_time_limit(0.).solve();
+        assert!(matches!(expired.err(), Some(ResolutionError::Other(_))));
+        let solution = make().with_time_limit(60.).solve().unwrap();
+        assert_eq!(solution.status(), SolutionStatus::Optimal);
+        assert_eq!(solution.eval(x + y), 3.);
+    }
 }
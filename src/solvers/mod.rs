@@ -79,7 +79,8 @@ where
 
 /// Whether to search for the variable values that give the highest
 /// or the lowest value of the objective function.
-#[derive(Eq, PartialEq, Clone, Copy)]
+#[derive(Debug, Eq, PartialEq, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum ObjectiveDirection {
     /// Find the highest possible value of the objective
     Maximisation,
@@ -139,6 +140,17 @@ impl From<String> for ResolutionError {
 
 impl Error for ResolutionError {}
 
+/// The error returned by the solvers that do not support
+/// [quadratic objectives](crate::QuadraticExpression)
+#[cfg(any(
+    feature = "coin_cbc",
+    feature = "minilp",
+    feature = "lpsolve",
+    feature = "lp-solvers"
+))]
+pub(crate) const UNSUPPORTED_QUADRATIC_OBJECTIVE: ResolutionError =
+    ResolutionError::Other("this solver does not support quadratic objectives");
+
 /// Represents an error setting the MIP gap
 
 #[derive(Debug, PartialEq, Clone)]
@@ -165,6 +177,30 @@ impl Display for MipGapError {
 
 impl Error for MipGapError {}
 
+/// The status of a [Solution]: whether it is optimal, or only feasible
+/// because the solver stopped before it could prove optimality.
+#[derive(Debug, Eq, PartialEq, Clone, Copy)]
+pub enum SolutionStatus {
+    /// The solution is proven to be optimal
+    Optimal,
+    /// The solver stopped because the gap between the solution and the best bound
+    /// on the objective was smaller than the requested [MIP gap](WithMipGap)
+    GapLimit,
+    /// The solver stopped because the time limit was reached.
+    /// The solution is feasible, but may not be optimal.
+    TimeLimit,
+    /// The solver stopped because of another limit (number of nodes, iterations, solutions...).
+    /// The solution is feasible, but may not be optimal.
+    Feasible,
+}
+
+impl SolutionStatus {
+    /// Whether the solution is proven to be optimal
+    pub fn is_optimal(self) -> bool {
+        self == SolutionStatus::Optimal
+    }
+}
+
 /// A solver's own representation of a model, to which constraints can be added.
 pub trait SolverModel {
     /// The type of the solution to the problem
@@ -196,6 +232,21 @@ pub trait Solution {
     /// Get the optimal value of a variable of the problem
     fn value(&self, variable: Variable) -> f64;
 
+    /// Whether the solution is proven to be optimal,
+    /// or the solver stopped early because of a limit.
+    ///
+    /// ```
+    /// use good_lp::*;
+    /// use good_lp::solvers::SolutionStatus;
+    /// variables!{vars: 0 <= x <= 3;}
+    /// let solution = vars.maximise(x).using(default_solver).solve()?;
+    /// assert_eq!(solution.status(), SolutionStatus::Optimal);
+    /// # Ok::<_, ResolutionError>(())
+    /// ```
+    fn status(&self) -> SolutionStatus {
+        SolutionStatus::Optimal
+    }
+
     /// ## Example
     ///
     /// ```rust
@@ -226,6 +277,64 @@ impl<N: Into<f64> + Clone> Solution for HashMap<Variable, N> {
     }
 }
 
+/// Information about the objective of a solution returned by a solver,
+/// and about how far from optimal it may be.
+///
+/// ```
+/// use good_lp::*;
+/// variables!{vars: 0 <= x <= 3;}
+/// let solution = vars.maximise(2 * x + 1).using(default_solver).solve()?;
+/// assert_eq!(solution.objective_value(), 7.);
+/// assert_eq!(solution.best_bound(), 7.);
+/// assert_eq!(solution.gap(), 0.);
+/// # Ok::<_, ResolutionError>(())
+/// ```
+pub trait SolutionInfo: Solution {
+    /// The value of the objective function for this solution, including its constant term
+    fn objective_value(&self) -> f64;
+
+    /// The best bound on the objective that the solver could prove:
+    /// no solution has an objective lower than this value when minimising,
+    /// or higher than this value when maximising.
+    ///
+    /// When the solution is [optimal](SolutionStatus::Optimal), this is the objective value.
+    /// When the solver stopped early without a bound, this is an infinite value.
+    fn best_bound(&self) -> f64;
+
+    /// The relative gap between the objective value and the best bound,
+    /// `|objective_value - best_bound| / |objective_value|`.
+    ///
+    /// It is 0 for optimal solutions, and infinite when the objective value is 0
+    /// but the bound isn't, or when the bound is infinite.
+    fn gap(&self) -> f64 {
+        let value = self.objective_value();
+        let bound = self.best_bound();
+        if value == bound {
+            0.
+        } else if value == 0. || bound.is_infinite() {
+            f64::INFINITY
+        } else {
+            (value - bound).abs() / value.abs()
+        }
+    }
+}
+
+/// The best bound of a solution that the solver could not improve on:
+/// the objective value itself if the solution is optimal,
+/// or the infinite bound that holds for any problem otherwise.
+#[cfg(any(feature = "highs", feature = "lpsolve", feature = "lp-solvers"))]
+pub(crate) fn default_best_bound(
+    objective_value: f64,
+    status: SolutionStatus,
+    direction: ObjectiveDirection,
+) -> f64 {
+    match (status, direction) {
+        (SolutionStatus::Optimal, _) => objective_value,
+        (_, ObjectiveDirection::Minimisation) => f64::NEG_INFINITY,
+        (_, ObjectiveDirection::Maximisation) => f64::INFINITY,
+    }
+}
+
 /// A type that contains the dual values of a solution.
 /// See [SolutionWithDual].
 pub trait DualValues {
@@ -239,6 +348,11 @@ pub trait DualValues {
 /// increase in the variable's value. The dual value for a constraint is nonzero only when
 /// the constraint is equal to its bound. Also known as the shadow price.
 /// This trait handles the retrieval of dual values from a solver.
+///
+/// It is implemented for HiGHS, lp_solve and minilp.
+/// It is not implemented for cbc, because coin_cbc 0.1.9 has its `row_price` function
+/// commented out until the C API of cbc provides it,
+/// nor for SCIP, whose [russcip](https://docs.rs/russcip) bindings do not expose dual values.
 pub trait SolutionWithDual<'a> {
     /// Type of the object containing the dual values.
     type Dual: DualValues;
@@ -248,6 +362,38 @@ pub trait SolutionWithDual<'a> {
     fn compute_dual(&'a mut self) -> Self::Dual;
 }
 
+/// A solution that contains the reduced costs of the variables.
+///
+/// The reduced cost of a variable is the increase in the objective function's value
+/// per unit increase in the variable's value, taking into account the changes it requires
+/// in the other variables. It is nonzero only when the variable is equal to one of its bounds,
+/// and is to the bounds of the variables what the [dual value](DualValues::dual)
+/// is to the constraints.
+///
+/// It is implemented for HiGHS and lp_solve, and for cbc when the `cbc-310` feature
+/// is activated, since it requires cbc 3.10 or newer.
+/// The bindings used for SCIP do not expose reduced costs.
+///
+/// ```
+/// use good_lp::*;
+/// # #[cfg(feature = "highs")] {
+/// variables! {vars: 0 <= x; 0 <= y;}
+/// let solution = vars
+///     .minimise(2 * x + 3 * y)
+///     .using(highs)
+///     .with(constraint!(x + y >= 4))
+///     .solve()?;
+/// assert_eq!(solution.value(y), 0.);
+/// // Increasing y by 1 costs 3, but saves 2 by decreasing x
+/// assert_eq!(solution.reduced_cost(y), 1.);
+/// # }
+/// # Ok::<_, ResolutionError>(())
+/// ```
+pub trait SolutionWithReducedCosts {
+    /// Get the reduced cost of a variable
+    fn reduced_cost(&self, variable: Variable) -> f64;
+}
+
 /// A model that supports [SOS type 1](https://en.wikipedia.org/wiki/Special_ordered_set) constraints.
 #[allow(clippy::upper_case_acronyms)]
 pub trait ModelWithSOS1 {
@@ -283,6 +429,143 @@ pub trait ModelWithSOS1 {
     }
 }
 
+/// A model that supports [SOS type 2](https://en.wikipedia.org/wiki/Special_ordered_set) constraints.
+///
+/// It is implemented for lp_solve. It is not implemented for cbc, that ignores SOS constraints
+/// on continuous variables. SOS2 constraints are mostly used to model
+/// [piecewise-linear functions](crate::PiecewiseLinear).
+#[allow(clippy::upper_case_acronyms)]
+pub trait ModelWithSOS2 {
+    /// Adds a constraint saying that at most two variables from the given set can be non-zero,
+    /// and that they must be consecutive in the order given by their weights.
+    ///
+    /// ```
+    /// use good_lp::*;
+    /// # // Not all solvers support SOS constraints
+    /// # #[cfg(feature = "lpsolve")] {
+    /// variables! {problem:
+    ///     0 <= x <= 2;
+    ///     0 <= y <= 3;
+    ///     0 <= z <= 4;
+    /// }
+    /// let solution = problem
+    ///     .maximise(x + y + z)
+    ///     .using(lp_solve)
+    ///     .with(constraint!(x + y + z <= 8))
+    ///     .with_sos2(x + 2 * y + 3 * z) // x and z cannot be non-zero at once
+    ///     .solve().unwrap();
+    /// assert_eq!(solution.value(x), 0.);
+    /// assert_eq!(solution.value(y), 3.);
+    /// assert_eq!(solution.value(z), 4.);
+    /// # }
+    /// ```
+    fn add_sos2<I: IntoAffineExpression>(&mut self, variables_and_weights: I);
+
+    /// See [ModelWithSOS2::add_sos2]
+    fn with_sos2<I: IntoAffineExpression>(mut self, variables_and_weights: I) -> Self
+    where
+        Self: Sized,
+    {
+        self.add_sos2(variables_and_weights);
+        self
+    }
+}
+
+/// A model that can be modified after it has been solved, and solved again.
+///
+/// Contrarily to [SolverModel::solve], [ModifiableModel::resolve] does not consume the model,
+/// and solvers that support it start from the previous solution.
+///
+/// It is implemented for cbc, HiGHS, lp_solve and minilp.
+/// It is not implemented for SCIP: its [russcip](https://docs.rs/russcip) bindings consume
+/// the model when solving it, and do not expose `SCIPfreeTransform` nor the functions that
+/// change bounds and objective coefficients, so a solved SCIP model cannot be changed.
+///
+/// ```
+/// use good_lp::*;
+/// # // Not all solvers can modify their models
+/// # #[cfg(any(feature = "coin_cbc", feature = "minilp"))] {
+/// variables! {vars: 0 <= x <= 10; 0 <= y <= 10;}
+/// let mut model = vars.maximise(x + y).using(default_solver);
+/// let capacity = model.add_constraint(constraint!(x + 2 * y <= 8));
+/// assert_eq!(model.resolve()?.value(x), 8.);
+///
+/// model.set_objective_coefficient(x, 0.);
+/// model.set_constraint_bounds(&capacity, f64::NEG_INFINITY, 12.);
+/// assert_eq!(model.resolve()?.value(y), 6.);
+///
+/// model.remove_constraint(&capacity);
+/// model.set_variable_bounds(y, 0., 3.);
+/// assert_eq!(model.resolve()?.value(y), 3.);
+/// # }
+/// # Ok::<_, ResolutionError>(())
+/// ```
+pub trait ModifiableModel: SolverModel {
+    /// Change the coefficient of a variable in the objective function
+    fn set_objective_coefficient(&mut self, variable: Variable, coefficient: f64);
+
+    /// Change the bounds of a variable.
+    /// Use infinite values for variables that are unbounded on one side.
+    fn set_variable_bounds(&mut self, variable: Variable, min: f64, max: f64);
+
+    /// Change the right-hand side of a constraint, by setting the bounds of the linear part
+    /// of its expression. Constraints are stored as `expression <= 0` or `expression == 0`:
+    /// `x + y <= 4` has a linear part of `x + y` with bounds `(-inf, 4)`,
+    /// and `x + y >= 2` is stored as `2 - x - y <= 0`, whose linear part `-x - y`
+    /// has bounds `(-inf, -2)`. An equality has equal bounds.
+    fn set_constraint_bounds(&mut self, constraint: &ConstraintReference, lower: f64, upper: f64);
+
+    /// Remove a constraint from the model. The references to the other constraints stay valid,
+    /// but the reference to the removed constraint must not be used anymore.
+    fn remove_constraint(&mut self, constraint: &ConstraintReference);
+
+    /// Solve the problem with its current modifications, keeping the model for further changes
+    fn resolve(&mut self) -> Result<Self::Solution, Self::Error>;
+}
+
+/// A model that accepts an initial solution, also known as a MIP start.
+/// A good feasible solution found by a heuristic or a previous resolution
+/// lets the solver prune its search tree earlier.
+///
+/// The initial solution is only a hint: if it is infeasible, it is ignored.
+/// The values of a previous [Solution] can be given with
+/// `variables.iter().map(|&v| (v, solution.value(v)))`.
+/// It is implemented for cbc, HiGHS, SCIP and minilp.
+///
+/// ```
+/// use good_lp::*;
+/// use std::collections::HashMap;
+/// # // Not all solvers accept initial solutions
+/// # #[cfg(any(feature = "coin_cbc", feature = "minilp"))] {
+/// variables! {vars: 0 <= x (integer) <= 10; 0 <= y (integer) <= 10;}
+/// let start: HashMap<Variable, f64> = vec![(x, 3.), (y, 1.)].into_iter().collect();
+/// let solution = vars
+///     .maximise(x + y)
+///     .using(default_solver)
+///     .with(constraint!(2 * x + 2 * y <= 9))
+///     .with_initial_solution(start)
+///     .solve()?;
+/// assert_eq!(solution.eval(x + y), 4.);
+/// # }
+/// # Ok::<_, ResolutionError>(())
+/// ```
+pub trait WithInitialSolution {
+    /// Give the solver a starting value for some of the variables.
+    /// Solvers differ in how they complete a partial solution:
+    /// cbc and SCIP set the missing variables to zero,
+    /// while HiGHS and minilp try to find values for them.
+    fn set_initial_solution<I: IntoIterator<Item = (Variable, f64)>>(&mut self, solution: I);
+
+    /// See [WithInitialSolution::set_initial_solution]
+    fn with_initial_solution<I: IntoIterator<Item = (Variable, f64)>>(mut self, solution: I) -> Self
+    where
+        Self: Sized,
+    {
+        self.set_initial_solution(solution);
+        self
+    }
+}
+
 /// A model that supports setting the MIP gap
 ///
 /// Setting the MIP gap can cause the solver to return a solution faster at the
@@ -385,3 +668,99 @@ pub trait WithMipGap {
     where
         Self: Sized;
 }
+
+/// A model that supports limiting the time spent by the solver.
+///
+/// When the limit is reached, the solver returns the best solution found so far,
+/// with the [SolutionStatus::TimeLimit] status.
+pub trait WithTimeLimit {
+    /// Get the time limit, in seconds
+    fn time_limit(&self) -> Option<f64>;
+
+    /// Set the maximum time the solver may spend on the problem, in seconds
+    ///
+    /// ```
+    /// use good_lp::*;
+    /// # // Not all solvers support time limits
+    /// # #[cfg(any(feature = "coin_cbc", feature = "minilp"))] {
+    /// variables!{vars: 0 <= x (integer) <= 3;}
+    /// let model = vars.maximise(x).using(default_solver).with_time_limit(60.);
+    /// assert_eq!(model.time_limit(), Some(60.));
+    /// let solution = model.solve().unwrap();
+    /// assert_eq!(solution.value(x), 3.);
+    /// # }
+    /// ```
+    fn with_time_limit(self, seconds: f64) -> Self
+    where
+        Self: Sized;
+}
+
+/// A model that supports setting the number of threads used by the solver
+pub trait WithThreads {
+    /// Get the number of threads
+    fn threads(&self) -> Option<u32>;
+
+    /// Set the number of threads the solver may use
+    ///
+    /// ```
+    /// use good_lp::*;
+    /// # #[cfg(feature = "coin_cbc")] {
+    /// variables!{vars: 0 <= x (integer) <= 3;}
+    /// let model = vars.maximise(x).using(default_solver).with_threads(2);
+    /// assert_eq!(model.threads(), Some(2));
+    /// # }
+    /// ```
+    fn with_threads(self, threads: u32) -> Self
+    where
+        Self: Sized;
+}
+
+/// A model that supports turning the solver's logs on and off
+pub trait WithVerbosity {
+    /// Whether the solver displays information about its progress
+    fn verbose(&self) -> bool;
+
+    /// Set whether the solver should display information about its progress
+    ///
+    /// ```
+    /// use good_lp::*;
+    /// # #[cfg(feature = "coin_cbc")] {
+    /// variables!{vars: 0 <= x <= 3;}
+    /// let model = vars.maximise(x).using(default_solver).with_verbosity(false);
+    /// assert!(!model.verbose());
+    /// # }
+    /// ```
+    fn with_verbosity(self, verbose: bool) -> Self
+    where
+        Self: Sized;
+}
+
+/// A model that supports setting the seed of the solver's random number generator.
+///
+/// Solvers make some choices at random, so changing the seed can change the time spent solving
+/// the problem, and the solution found when there are several optimal ones.
+pub trait WithRandomSeed {
+    /// Get the random seed
+    fn random_seed(&self) -> Option<u32>;
+
+    /// Set the random seed.
+    /// Solvers only accept seeds up to `i32::MAX`: the highest bit of larger seeds is ignored.
+    ///
+    /// ```
+    /// use good_lp::*;
+    /// # #[cfg(feature = "coin_cbc")] {
+    /// variables!{vars: 0 <= x <= 3;}
+    /// let model = vars.maximise(x).using(default_solver).with_random_seed(42);
+    /// assert_eq!(model.random_seed(), Some(42));
+    /// # }
+    /// ```
+    fn with_random_seed(self, seed: u32) -> Self
+    where
+        Self: Sized;
+}
+
+/// The seed given to solvers that take it as a (positive) C int
+#[cfg(any(feature = "coin_cbc", feature = "highs", feature = "scip"))]
+pub(crate) fn c_int_seed(seed: u32) -> i32 {
+    (seed & i32::MAX as u32) as i32
+}
@@ -9,14 +9,20 @@ use russcip::model::ModelWithProblem;
 use russcip::model::ObjSense;
 use russcip::model::ProblemCreated;
 use russcip::model::Solved;
+use russcip::status::Status;
 use russcip::variable::VarType;
 use russcip::ProblemOrSolving;
 use russcip::WithSolutions;
 
+use crate::quadratic_expression::QuadraticTerms;
 use crate::variable::{UnsolvedProblem, VariableDefinition};
 use crate::{
     constraint::ConstraintReference,
-    solvers::{ObjectiveDirection, ResolutionError, Solution, SolverModel},
+    solvers::{
+        c_int_seed, ObjectiveDirection, ResolutionError, Solution, SolutionInfo, SolutionStatus,
+        SolverModel, WithInitialSolution, WithRandomSeed, WithThreads, WithTimeLimit,
+        WithVerbosity,
+    },
     CardinalityConstraintSolver,
 };
 use crate::{Constraint, Variable};
@@ -61,15 +67,36 @@ pub fn scip(to_solve: UnsolvedProblem) -> SCIPProblem {
     SCIPProblem {
         model: model,
         id_for_var: var_map,
+        direction: to_solve.direction,
+        objective_constant: to_solve.objective.constant,
+        quadratic_objective: to_solve.quadratic_objective,
+        options: SCIPOptions::default(),
     }
 }
 
 /// A SCIP Model
+///
+/// Solving consumes the model, which cannot be [modified](crate::ModifiableModel)
+/// and solved again.
 pub struct SCIPProblem {
     // the underlying SCIP model representing the problem
     model: Model<ProblemCreated>,
     // map from good_lp variables to SCIP variable ids
     id_for_var: HashMap<Variable, Rc<russcip::Variable>>,
+    direction: ObjectiveDirection,
+    objective_constant: f64,
+    // the products of variables in the objective, added to the model when solving
+    quadratic_objective: QuadraticTerms,
+    options: SCIPOptions,
+}
+
+/// The options that were set on the model, which cannot be read back from SCIP
+#[derive(Debug, Clone, Copy, Default)]
+struct SCIPOptions {
+    time_limit: Option<f64>,
+    threads: Option<u32>,
+    verbose: bool,
+    random_seed: Option<u32>,
 }
 
 impl SCIPProblem {
@@ -96,7 +123,121 @@ impl CardinalityConstraintSolver for SCIPProblem {
         self.model
             .add_cons_cardinality(scip_vars, rhs, format!("cardinality{}", index).as_str());
 
-        ConstraintReference { index }
+        ConstraintReference::new(index, None)
+    }
+}
+
+/// Every call gives a new solution to SCIP, in which the variables without a value are zero.
+/// Solutions that SCIP finds infeasible are discarded.
+impl WithInitialSolution for SCIPProblem {
+    fn set_initial_solution<I: IntoIterator<Item = (Variable, f64)>>(&mut self, solution: I) {
+        let sol = self.model.create_sol();
+        for (var, value) in solution {
+            sol.set_val(Rc::clone(&self.id_for_var[&var]), value);
+        }
+        // An initial solution is only a hint
+        let _ = self.model.add_sol(sol);
+    }
+}
+
+impl WithTimeLimit for SCIPProblem {
+    fn time_limit(&self) -> Option<f64> {
+        self.options.time_limit
+    }
+
+    fn with_time_limit(mut self, seconds: f64) -> Self {
+        self.model = self
+            .model
+            .set_real_param("limits/time", seconds)
+            .expect("invalid time limit");
+        self.options.time_limit = Some(seconds);
+        self
+    }
+}
+
+impl WithThreads for SCIPProblem {
+    fn threads(&self) -> Option<u32> {
+        self.options.threads
+    }
+
+    /// SCIP itself is single-threaded: this sets the number of threads used to solve
+    /// the linear relaxations of the problem.
+    fn with_threads(mut self, threads: u32) -> Self {
+        self.model = self
+            .model
+            .set_int_param("lp/threads", threads as i32)
+            .expect("invalid number of threads");
+        self.options.threads = Some(threads);
+        self
+    }
+}
+
+impl WithVerbosity for SCIPProblem {
+    fn verbose(&self) -> bool {
+        self.options.verbose
+    }
+
+    fn with_verbosity(mut self, verbose: bool) -> Self {
+        // 4 is the default SCIP verbosity level, 0 hides all output
+        self.model = self
+            .model
+            .set_int_param("display/verblevel", if verbose { 4 } else { 0 })
+            .expect("invalid verbosity level");
+        self.options.verbose = verbose;
+        self
+    }
+}
+
+impl WithRandomSeed for SCIPProblem {
+    fn random_seed(&self) -> Option<u32> {
+        self.options.random_seed
+    }
+
+    fn with_random_seed(mut self, seed: u32) -> Self {
+        self.model = self
+            .model
+            .set_int_param("randomization/randomseedshift", c_int_seed(seed))
+            .expect("invalid random seed");
+        self.options.random_seed = Some(seed);
+        self
+    }
+}
+
+impl SCIPProblem {
+    /// SCIP only accepts linear objectives, so the quadratic part of the objective
+    /// is moved to a new variable, bounded by a quadratic constraint
+    fn add_quadratic_objective(&mut self) {
+        let terms = self.quadratic_objective.sorted();
+        let objective = self.model.add_var(
+            f64::NEG_INFINITY,
+            f64::INFINITY,
+            1.,
+            "quadratic_objective",
+            VarType::Continuous,
+        );
+        let (lhs, rhs) = match self.direction {
+            ObjectiveDirection::Minimisation => (f64::NEG_INFINITY, 0.),
+            ObjectiveDirection::Maximisation => (0., f64::INFINITY),
+        };
+        let quad_vars_1 = terms
+            .iter()
+            .map(|(a, _, _)| Rc::clone(&self.id_for_var[a]))
+            .collect();
+        let quad_vars_2 = terms
+            .iter()
+            .map(|(_, b, _)| Rc::clone(&self.id_for_var[b]))
+            .collect();
+        let mut quad_coefs: Vec<f64> = terms.iter().map(|&(_, _, c)| c).collect();
+        self.model.add_cons_quadratic(
+            vec![objective],
+            &mut [-1.],
+            quad_vars_1,
+            quad_vars_2,
+            &mut quad_coefs,
+            lhs,
+            rhs,
+            "quadratic_objective",
+        );
     }
 }
 
@@ -104,35 +245,48 @@ impl SolverModel for SCIPProblem {
     type Solution = SCIPSolved;
     type Error = ResolutionError;
 
-    fn solve(self) -> Result<Self::Solution, Self::Error> {
+    fn solve(mut self) -> Result<Self::Solution, Self::Error> {
+        if !self.quadratic_objective.is_empty() {
+            self.add_quadratic_objective();
+        }
         let solved_model = self.model.solve();
-        let status = solved_model.status();
-        match status {
-            russcip::status::Status::Optimal => Ok(SCIPSolved {
-                solved_problem: solved_model,
-                id_for_var: self.id_for_var,
-            }),
-            russcip::status::Status::Infeasible => {
-                return Err(ResolutionError::Infeasible);
-            }
-            russcip::status::Status::Unbounded => {
-                return Err(ResolutionError::Unbounded);
-            }
+        let status = match solved_model.status() {
+            Status::Optimal => SolutionStatus::Optimal,
+            Status::Infeasible => return Err(ResolutionError::Infeasible),
+            Status::Unbounded => return Err(ResolutionError::Unbounded),
+            Status::GapLimit => SolutionStatus::GapLimit,
+            Status::TimeLimit => SolutionStatus::TimeLimit,
+            _ if solved_model.n_sols() > 0 => SolutionStatus::Feasible,
             other_status => {
                 return Err(ResolutionError::Str(format!(
                     "Unexpected status {:?}",
                     other_status
                 )));
             }
+        };
+        if solved_model.n_sols() == 0 {
+            return Err(ResolutionError::Str(format!(
+                "No solution found before stopping: {:?}",
+                solved_model.status()
+            )));
         }
+        let objective_value = solved_model.obj_val() + self.objective_constant;
+        let best_bound = if status.is_optimal() {
+            objective_value
+        } else {
+            dual_bound(&solved_model) + self.objective_constant
+        };
+        Ok(SCIPSolved {
+            solved_problem: solved_model,
+            id_for_var: self.id_for_var,
+            status,
+            objective_value,
+            best_bound,
+        })
     }
 
     fn add_constraint(&mut self, c: Constraint) -> ConstraintReference {
-        let constant = -c.expression.constant;
-        let lhs = match c.is_equality {
-            true => constant,
-            false => -f64::INFINITY,
-        };
+        let (lhs, rhs) = c.bounds();
 
         let n_vars_in_cons = c.expression.linear.coefficients.len();
         let mut vars_in_cons = Vec::with_capacity(n_vars_in_cons);
@@ -144,15 +298,11 @@ impl SolverModel for SCIPProblem {
         }
 
         let index = self.model.n_conss() + 1;
-        self.model.add_cons(
-            vars_in_cons,
-            &coeffs,
-            lhs,
-            constant,
-            format!("c{}", index).as_str(),
-        );
+        let name = c.name.clone().unwrap_or_else(|| format!("c{}", index));
+        self.model
+            .add_cons(vars_in_cons, &coeffs, lhs, rhs, name.as_str());
 
-        ConstraintReference { index }
+        ConstraintReference::new(index, c.name)
     }
 
     fn name() -> &'static str {
@@ -160,10 +310,20 @@ impl SolverModel for SCIPProblem {
     }
 }
 
+/// The bound on the objective that SCIP proved, also when it stopped before optimality
+#[allow(unsafe_code)]
+fn dual_bound(model: &Model<Solved>) -> f64 {
+    // SAFETY: the pointer is valid while the model lives, and is only read
+    unsafe { russcip::ffi::SCIPgetDualbound(model.scip_ptr()) }
+}
+
 /// A wrapper to a solved SCIP problem
 pub struct SCIPSolved {
     solved_problem: Model<Solved>,
     id_for_var: HashMap<Variable, Rc<russcip::Variable>>,
+    status: SolutionStatus,
+    objective_value: f64,
+    best_bound: f64,
 }
 
 impl Solution for SCIPSolved {
@@ -175,6 +335,20 @@ impl Solution for SCIPSolved {
         let id = Rc::clone(&self.id_for_var[&var]);
         sol.val(id)
     }
+
+    fn status(&self) -> SolutionStatus {
+        self.status
+    }
+}
+
+impl SolutionInfo for SCIPSolved {
+    fn objective_value(&self) -> f64 {
+        self.objective_value
+    }
+
+    fn best_bound(&self) -> f64 {
+        self.best_bound
+    }
 }
 
 #[cfg(test)]
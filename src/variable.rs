@@ -10,8 +10,10 @@ use std::ops::{Div, Mul, Neg, Not, RangeBounds};
 use fnv::FnvHashMap as HashMap;
 
 use crate::affine_expression_trait::IntoAffineExpression;
+use crate::constraint::{Constraint, ConstraintReference};
 use crate::expression::{Expression, LinearExpression};
-use crate::solvers::{ObjectiveDirection, Solver};
+use crate::quadratic_expression::{IntoObjective, QuadraticExpression, QuadraticTerms};
+use crate::solvers::{ObjectiveDirection, Solver, SolverModel};
 
 /// A variable in a problem. Use variables to create [expressions](Expression),
 /// to express the [objective](ProblemVariables::optimise)
@@ -35,6 +37,11 @@ use crate::solvers::{ObjectiveDirection, Solver};
 /// assert_eq!(v1, v1_copy);
 /// ```
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(transparent)
+)]
 pub struct Variable {
     /// A variable is nothing more than an index into the `variables` field of a ProblemVariables
     /// That's why it can be `Copy`.
@@ -79,8 +86,8 @@ impl<'a> IntoAffineExpression for &'a Variable {
 }
 
 impl Variable {
-    /// No one should use this method outside of [VariableDefinition]
-    fn at(index: usize) -> Self {
+    /// Only [ProblemVariables] and the models that add their own variables should use this method
+    pub(crate) fn at(index: usize) -> Self {
         Self { index }
     }
 }
@@ -115,8 +122,11 @@ impl FormatWithVars for Variable {
 
 /// Defines the properties of a variable, such as its lower and upper bounds.
 #[derive(Clone, PartialEq, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct VariableDefinition {
+    #[cfg_attr(feature = "serde", serde(with = "serde_bounds::lower"))]
     pub(crate) min: f64,
+    #[cfg_attr(feature = "serde", serde(with = "serde_bounds::upper"))]
     pub(crate) max: f64,
     pub(crate) name: String,
     pub(crate) is_integer: bool,
@@ -143,11 +153,9 @@ impl VariableDefinition {
     /// # use good_lp::{ProblemVariables, variable, default_solver, SolverModel, Solution};
     /// let mut problem = ProblemVariables::new();
     /// let x = problem.add(variable().integer().min(0).max(2.5));
-    /// # if cfg!(not(feature = "minilp")) {
     /// let solution = problem.maximise(x).using(default_solver).solve().unwrap();
     /// // x is bound to [0; 2.5], but the solution is x=2 because x needs to be an integer
     /// assert_eq!(solution.value(x), 2.);
-    /// # }
     /// ```
     pub fn integer(mut self) -> Self {
         self.is_integer = true;
@@ -164,11 +172,9 @@ impl VariableDefinition {
     /// let mut problem = ProblemVariables::new();
     /// let x = problem.add(variable().binary());
     /// let y = problem.add(variable().binary());
-    /// if cfg!(not(any(feature = "minilp"))) {
-    ///     let solution = problem.maximise(x + y).using(default_solver).solve().unwrap();
-    ///     assert_eq!(solution.value(x), 1.);
-    ///     assert_eq!(solution.value(y), 1.);
-    /// }
+    /// let solution = problem.maximise(x + y).using(default_solver).solve().unwrap();
+    /// assert_eq!(solution.value(x), 1.);
+    /// assert_eq!(solution.value(y), 1.);
     /// ```
     pub fn binary(mut self) -> Self {
         self.is_integer = true;
@@ -255,11 +261,39 @@ pub fn variable() -> VariableDefinition {
     VariableDefinition::default()
 }
 
+/// Infinite bounds are serialized as null,
+/// because formats such as JSON cannot represent infinite numbers.
+#[cfg(feature = "serde")]
+mod serde_bounds {
+    macro_rules! bound {
+        ($name:ident, $infinity:expr) => {
+            pub mod $name {
+                use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+                pub fn serialize<S: Serializer>(bound: &f64, s: S) -> Result<S::Ok, S::Error> {
+                    Some(*bound).filter(|b| b.is_finite()).serialize(s)
+                }
+
+                pub fn deserialize<'de, D: Deserializer<'de>>(d: D) -> Result<f64, D::Error> {
+                    Ok(Option::<f64>::deserialize(d)?.unwrap_or($infinity))
+                }
+            }
+        };
+    }
+    bound!(lower, f64::NEG_INFINITY);
+    bound!(upper, f64::INFINITY);
+}
+
 /// Represents the variables for a given problem.
 /// Each problem has a unique type, which prevents using the variables
 /// from one problem inside an other one.
 /// Instances of this type should be created exclusively using the [variables!] macro.
 #[derive(Default, Clone)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(transparent)
+)]
 pub struct ProblemVariables {
     variables: Vec<VariableDefinition>,
 }
@@ -326,19 +360,30 @@ impl ProblemVariables {
     /// assert_eq!(solve(ObjectiveDirection::Minimisation), 2.);
     /// assert_eq!(solve(ObjectiveDirection::Maximisation), 3.);
     /// ```
-    pub fn optimise<E: IntoAffineExpression>(
+    ///
+    /// The objective can also be a [QuadraticExpression](crate::QuadraticExpression),
+    /// for the solvers that support it.
+    pub fn optimise<E: IntoObjective>(
         self,
         direction: ObjectiveDirection,
         objective: E,
     ) -> UnsolvedProblem {
-        let objective = Expression::from_other_affine(objective);
+        let QuadraticExpression {
+            quadratic,
+            affine: objective,
+        } = objective.into_quadratic_expression();
         assert!(
-            objective.linear.coefficients.len() <= self.variables.len(),
+            objective.linear.coefficients.len() <= self.variables.len()
+                && quadratic
+                    .coefficients
+                    .keys()
+                    .all(|&(_, b)| b.index() < self.variables.len()),
             "There should not be more variables in the objective function than in the problem. \
             You probably used variables from a different problem in this one."
         );
         UnsolvedProblem {
             objective,
+            quadratic_objective: quadratic,
             direction,
             variables: self,
         }
@@ -352,7 +397,7 @@ impl ProblemVariables {
     /// let solution = problem.maximise(x).using(default_solver).solve().unwrap();
     /// assert_eq!(solution.value(x), 7.);
     /// ```
-    pub fn maximise<E: IntoAffineExpression>(self, objective: E) -> UnsolvedProblem {
+    pub fn maximise<E: IntoObjective>(self, objective: E) -> UnsolvedProblem {
         self.optimise(ObjectiveDirection::Maximisation, objective)
     }
 
@@ -363,7 +408,7 @@ impl ProblemVariables {
     /// let solution = problem.minimise(x).using(default_solver).solve().unwrap();
     /// assert_eq!(solution.value(x), -8.);
     /// ```
-    pub fn minimise<E: IntoAffineExpression>(self, objective: E) -> UnsolvedProblem {
+    pub fn minimise<E: IntoObjective>(self, objective: E) -> UnsolvedProblem {
         self.optimise(ObjectiveDirection::Minimisation, objective)
     }
 
@@ -375,6 +420,34 @@ impl ProblemVariables {
             .map(|(i, def)| (Variable::at(i), def))
     }
 
+    /// The definition of a variable of the problem
+    pub(crate) fn definition(&self, variable: Variable) -> &VariableDefinition {
+        &self.variables[variable.index()]
+    }
+
+    /// The largest value of `sign * expression` given the bounds of the variables.
+    /// Returns the first variable with an infinite bound in the relevant direction, if any.
+    pub(crate) fn extreme_value(
+        &self,
+        expression: &Expression,
+        sign: f64,
+    ) -> Result<f64, Variable> {
+        let mut value = sign * expression.constant;
+        for (&var, &coefficient) in &expression.linear.coefficients {
+            let def = self.definition(var);
+            let bound = if coefficient * sign > 0. {
+                def.max
+            } else {
+                def.min
+            };
+            if !bound.is_finite() {
+                return Err(var);
+            }
+            value += coefficient * sign * bound;
+        }
+        Ok(value)
+    }
+
     /// The number of variables
     pub fn len(&self) -> usize {
         self.variables.len()
@@ -432,8 +505,20 @@ impl IntoIterator for ProblemVariables {
 
 /// A problem without constraints.
 /// Created with [ProblemVariables::optimise].
+#[derive(Clone)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(try_from = "serde_problem::UncheckedUnsolvedProblem")
+)]
 pub struct UnsolvedProblem {
     pub(crate) objective: Expression,
+    /// The products of variables in the objective, if it is quadratic
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "QuadraticTerms::is_empty")
+    )]
+    pub(crate) quadratic_objective: QuadraticTerms,
     pub(crate) direction: ObjectiveDirection,
     pub(crate) variables: ProblemVariables,
 }
@@ -443,6 +528,228 @@ impl UnsolvedProblem {
     pub fn using<S: Solver>(self, mut solver: S) -> S::Model {
         solver.create_model(self)
     }
+
+    /// Add a constraint to the problem without choosing a solver yet.
+    /// This returns a [ProblemDescription], that stores the constraints in memory.
+    ///
+    /// ```
+    /// use good_lp::*;
+    /// variables! {vars: 0 <= x; 0 <= y;}
+    /// let problem = vars.maximise(x + y).with(constraint!(x + 2 * y <= 4));
+    /// assert_eq!(problem.constraints().len(), 1);
+    /// ```
+    pub fn with(self, constraint: Constraint) -> ProblemDescription {
+        ProblemDescription::new(self).with(constraint)
+    }
+}
+
+/// A complete problem, with its variables, objective and constraints,
+/// that is not tied to any particular solver.
+///
+/// Contrarily to a [SolverModel], a problem description can be cloned, inspected,
+/// and then fed to any number of solvers using [ProblemDescription::using].
+///
+/// ```
+/// # fn assert_float_eq(a:f64, b:f64) {
+/// #   assert!((a-b).abs() <= 1e-9, "{} != {}", a, b);
+/// # }
+/// use good_lp::*;
+/// variables! {vars: 0 <= x <= 3; 0 <= y <= 5;}
+/// let problem = vars
+///     .maximise(2 * x + y)
+///     .with(constraint!(x + y <= 4));
+///
+/// // The problem can be inspected before being solved
+/// for (reference, constraint) in problem.iter_constraints() {
+///     println!("{:?}: {}", reference, problem.variables().display(constraint));
+/// }
+///
+/// // ... and solved as many times as needed
+/// let solution = problem.clone().using(default_solver).solve()?;
+/// assert_float_eq(solution.value(x), 3.);
+/// assert_float_eq(solution.value(y), 1.);
+/// # Ok::<_, ResolutionError>(())
+/// ```
+#[derive(Clone)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(try_from = "serde_problem::UncheckedProblemDescription")
+)]
+pub struct ProblemDescription {
+    pub(crate) problem: UnsolvedProblem,
+    pub(crate) constraints: Vec<Constraint>,
+}
+
+impl ProblemDescription {
+    /// Create a problem description without any constraint
+    pub fn new(problem: UnsolvedProblem) -> Self {
+        ProblemDescription {
+            problem,
+            constraints: vec![],
+        }
+    }
+
+    /// Adds a constraint to the problem and returns a reference to it.
+    /// The reference stays valid once the problem is fed to a solver with [ProblemDescription::using].
+    pub fn add_constraint(&mut self, constraint: Constraint) -> ConstraintReference {
+        let reference = ConstraintReference::new(self.constraints.len(), constraint.name.clone());
+        self.constraints.push(constraint);
+        reference
+    }
+
+    /// Takes a problem and adds a constraint to it
+    pub fn with(mut self, constraint: Constraint) -> Self {
+        self.add_constraint(constraint);
+        self
+    }
+
+    /// The variables of the problem
+    pub fn variables(&self) -> &ProblemVariables {
+        &self.problem.variables
+    }
+
+    /// The objective function, without its products of variables if it is
+    /// [quadratic](crate::QuadraticExpression)
+    pub fn objective(&self) -> &Expression {
+        &self.problem.objective
+    }
+
+    /// Whether the objective should be maximised or minimised
+    pub fn direction(&self) -> ObjectiveDirection {
+        self.problem.direction
+    }
+
+    /// Whether the objective contains products of variables
+    pub fn has_quadratic_objective(&self) -> bool {
+        !self.problem.quadratic_objective.is_empty()
+    }
+
+    /// All the constraints of the problem, in the order they were added
+    pub fn constraints(&self) -> &[Constraint] {
+        &self.constraints
+    }
+
+    /// Iterates over the constraints of the problem, together with their references
+    pub fn iter_constraints(
+        &self,
+    ) -> impl Iterator<Item = (ConstraintReference, &Constraint)> + '_ {
+        self.constraints
+            .iter()
+            .enumerate()
+            .map(|(index, constraint)| {
+                let reference = ConstraintReference::new(index, constraint.name.clone());
+                (reference, constraint)
+            })
+    }
+
+    /// Get the constraint with the given reference
+    pub fn constraint(&self, reference: &ConstraintReference) -> &Constraint {
+        &self.constraints[reference.index]
+    }
+
+    /// Create a solver instance and feed it with this problem and all its constraints
+    pub fn using<S: Solver>(self, solver: S) -> S::Model {
+        let mut model = self.problem.using(solver);
+        for constraint in self.constraints {
+            model.add_constraint(constraint);
+        }
+        model
+    }
+}
+
+/// Deserialized problems are checked to only use the variables they define,
+/// so that an invalid input is rejected instead of making the solvers panic.
+#[cfg(feature = "serde")]
+mod serde_problem {
+    use std::convert::TryFrom;
+
+    use serde::Deserialize;
+
+    use super::{ProblemDescription, ProblemVariables, UnsolvedProblem, Variable};
+    use crate::constraint::Constraint;
+    use crate::expression::Expression;
+    use crate::quadratic_expression::QuadraticTerms;
+    use crate::solvers::ObjectiveDirection;
+
+    #[derive(Deserialize)]
+    pub struct UncheckedUnsolvedProblem {
+        objective: Expression,
+        #[serde(default)]
+        quadratic_objective: QuadraticTerms,
+        direction: ObjectiveDirection,
+        variables: ProblemVariables,
+    }
+
+    #[derive(Deserialize)]
+    pub struct UncheckedProblemDescription {
+        problem: UnsolvedProblem,
+        constraints: Vec<Constraint>,
+    }
+
+    fn check<I: IntoIterator<Item = Variable>>(
+        variables: &ProblemVariables,
+        used: I,
+    ) -> Result<(), String> {
+        match used.into_iter().find(|var| var.index() >= variables.len()) {
+            Some(var) => Err(format!(
+                "the variable v{} is used, but the problem only has {} variables",
+                var.index(),
+                variables.len()
+            )),
+            None => Ok(()),
+        }
+    }
+
+    impl TryFrom<UncheckedUnsolvedProblem> for UnsolvedProblem {
+        type Error = String;
+
+        fn try_from(problem: UncheckedUnsolvedProblem) -> Result<Self, Self::Error> {
+            let variables = &problem.variables;
+            check(
+                variables,
+                problem.objective.linear.coefficients.keys().copied(),
+            )?;
+            check(
+                variables,
+                problem
+                    .quadratic_objective
+                    .coefficients
+                    .keys()
+                    .flat_map(|&(a, b)| [a, b]),
+            )?;
+            Ok(UnsolvedProblem {
+                objective: problem.objective,
+                quadratic_objective: problem.quadratic_objective,
+                direction: problem.direction,
+                variables: problem.variables,
+            })
+        }
+    }
+
+    impl TryFrom<UncheckedProblemDescription> for ProblemDescription {
+        type Error = String;
+
+        fn try_from(description: UncheckedProblemDescription) -> Result<Self, Self::Error> {
+            let variables = &description.problem.variables;
+            for constraint in &description.constraints {
+                check(
+                    variables,
+                    constraint.expression.linear.coefficients.keys().copied(),
+                )?;
+            }
+            Ok(ProblemDescription {
+                problem: description.problem,
+                constraints: description.constraints,
+            })
+        }
+    }
+}
+
+impl From<UnsolvedProblem> for ProblemDescription {
+    fn from(problem: UnsolvedProblem) -> Self {
+        ProblemDescription::new(problem)
+    }
 }
 
 impl<N: Into<f64>> Mul<N> for Variable {
@@ -0,0 +1,56 @@
+use float_eq::assert_float_eq;
+
+use good_lp::{
+    variables, CardinalityConstraintSolver, ResolutionError, Solution, Solver, SolverModel,
+};
+
+/// The values found by SCIP, that supports cardinality constraints natively,
+/// for each maximum number of non-zero variables
+const SCIP_RESULTS: [(f64, f64, f64); 3] = [(0., 0., 0.), (2., 0., 0.), (2., 3., 0.)];
+
+#[allow(dead_code)]
+fn generic_cardinality<S>(solver: S)
+where
+    S: Solver + Clone,
+    S::Model: SolverModel<Error = ResolutionError> + CardinalityConstraintSolver,
+{
+    for (rhs, &(expected_x, expected_y, expected_z)) in SCIP_RESULTS.iter().enumerate() {
+        variables! {vars: 0 <= x <= 2; 0 <= y <= 3; -2 <= z <= 1;}
+        let mut model = vars.maximise(5 * x + 3 * y - 4 * z).using(solver.clone());
+        model.add_cardinality_constraint(&[x, y, z], rhs);
+        let solution = model.solve().unwrap();
+        assert_float_eq!(solution.value(x), expected_x, abs <= 1e-6);
+        assert_float_eq!(solution.value(y), expected_y, abs <= 1e-6);
+        assert_float_eq!(solution.value(z), expected_z, abs <= 1e-6);
+    }
+}
+
+#[cfg(feature = "coin_cbc")]
+#[test]
+fn cardinality_coin_cbc() {
+    generic_cardinality(good_lp::coin_cbc);
+}
+
+#[cfg(feature = "highs")]
+#[test]
+fn cardinality_highs() {
+    generic_cardinality(good_lp::highs);
+}
+
+#[cfg(feature = "lpsolve")]
+#[test]
+fn cardinality_lpsolve() {
+    generic_cardinality(good_lp::lp_solve);
+}
+
+#[cfg(feature = "minilp")]
+#[test]
+fn cardinality_minilp() {
+    generic_cardinality(good_lp::minilp);
+}
+
+#[cfg(feature = "scip")]
+#[test]
+fn cardinality_scip() {
+    generic_cardinality(good_lp::scip);
+}
@@ -0,0 +1,79 @@
+use good_lp::{
+    constraint, variable, variables, ProblemDescription, ResolutionError, Solver, SolverModel,
+    VariableBound,
+};
+
+#[cfg(feature = "coin_cbc")]
+use good_lp::coin_cbc;
+
+#[cfg(feature = "highs")]
+use good_lp::highs;
+
+#[cfg(feature = "minilp")]
+use good_lp::minilp;
+
+#[cfg(feature = "lpsolve")]
+use good_lp::lp_solve;
+
+#[cfg(feature = "scip")]
+use good_lp::scip;
+
+#[allow(dead_code)]
+fn generic_infeasible_subsystem<S>(solver: S)
+where
+    S: Solver + Copy,
+    S::Model: SolverModel<Error = ResolutionError>,
+{
+    let mut vars = variables!();
+    let a = vars.add(variable().integer().clamp(0, 10));
+    let b = vars.add(variable().min(0));
+    let c = vars.add(variable());
+    let mut problem = ProblemDescription::new(vars.maximise(a + b + c));
+    let ab = problem.add_constraint(constraint!(2 * a + 2 * b == 3).named("ab"));
+    problem.add_constraint(constraint!(-4 <= c <= 4));
+    let b_max = problem.add_constraint(constraint!(b <= 0.25));
+    problem.add_constraint(constraint!(a + c >= 1));
+
+    // 2a + 2b = 3 with a integer requires b >= 0.5
+    let iis = problem
+        .infeasible_subsystem(solver)
+        .unwrap()
+        .expect("the problem is infeasible");
+    assert_eq!(iis.constraints, [ab.clone(), b_max]);
+    assert_eq!(iis.bounds, [VariableBound::Lower(b)]);
+    assert_eq!(iis.constraints[0].name(), Some("ab"));
+
+    variables! {vars: 0 <= x <= 1;}
+    let problem = vars.maximise(x).with(constraint!(x <= 3));
+    assert_eq!(problem.infeasible_subsystem(solver).unwrap(), None);
+}
+
+#[cfg(feature = "coin_cbc")]
+#[test]
+fn infeasible_subsystem_coin_cbc() {
+    generic_infeasible_subsystem(coin_cbc);
+}
+
+#[cfg(feature = "highs")]
+#[test]
+fn infeasible_subsystem_highs() {
+    generic_infeasible_subsystem(highs);
+}
+
+#[cfg(feature = "minilp")]
+#[test]
+fn infeasible_subsystem_minilp() {
+    generic_infeasible_subsystem(minilp);
+}
+
+#[cfg(feature = "lpsolve")]
+#[test]
+fn infeasible_subsystem_lpsolve() {
+    generic_infeasible_subsystem(lp_solve);
+}
+
+#[cfg(feature = "scip")]
+#[test]
+fn infeasible_subsystem_scip() {
+    generic_infeasible_subsystem(scip);
+}
@@ -0,0 +1,85 @@
+use std::collections::HashMap;
+
+use good_lp::{
+    constraint, variable, variables, Expression, Solution, Solver, SolverModel, Variable,
+    WithInitialSolution,
+};
+
+#[cfg(feature = "coin_cbc")]
+use good_lp::coin_cbc;
+
+#[cfg(feature = "highs")]
+use good_lp::highs;
+
+#[cfg(feature = "minilp")]
+use good_lp::minilp;
+
+#[cfg(feature = "scip")]
+use good_lp::scip;
+
+#[allow(dead_code)]
+fn generic_initial_solution<S>(mut solver: S)
+where
+    S: Solver,
+    S::Model: WithInitialSolution,
+{
+    let weights = [12., 2., 1., 1., 4.];
+    let values = [4., 2., 1., 2., 10.];
+    let mut vars = variables!();
+    let take: Vec<Variable> = vars.add_vector(variable().binary(), weights.len());
+    let value: Expression = take.iter().zip(values).map(|(&t, v)| t * v).sum();
+    let weight: Expression = take.iter().zip(weights).map(|(&t, w)| t * w).sum();
+    let model = |vars: good_lp::ProblemVariables, solver: &mut S| {
+        solver
+            .create_model(vars.maximise(value.clone()))
+            .with(constraint!(weight.clone() <= 15))
+    };
+
+    // A feasible but suboptimal start
+    let start: HashMap<Variable, f64> = vec![(take[0], 1.), (take[1], 1.)].into_iter().collect();
+    let solution = model(vars.clone(), &mut solver)
+        .with_initial_solution(start)
+        .solve()
+        .unwrap();
+    assert_eq!(solution.eval(&value), 15.);
+
+    // An infeasible start is ignored
+    let start = take.iter().map(|&t| (t, 1.));
+    let solution = model(vars.clone(), &mut solver)
+        .with_initial_solution(start)
+        .solve()
+        .unwrap();
+    assert_eq!(solution.eval(&value), 15.);
+
+    // The optimal solution
+    let optimal: Vec<(Variable, f64)> = take.iter().map(|&t| (t, solution.value(t))).collect();
+    let solution = model(vars, &mut solver)
+        .with_initial_solution(optimal)
+        .solve()
+        .unwrap();
+    assert_eq!(solution.eval(&value), 15.);
+}
+
+#[cfg(feature = "coin_cbc")]
+#[test]
+fn initial_solution_coin_cbc() {
+    generic_initial_solution(coin_cbc);
+}
+
+#[cfg(feature = "highs")]
+#[test]
+fn initial_solution_highs() {
+    generic_initial_solution(highs);
+}
+
+#[cfg(feature = "minilp")]
+#[test]
+fn initial_solution_minilp() {
+    generic_initial_solution(minilp);
+}
+
+#[cfg(feature = "scip")]
+#[test]
+fn initial_solution_scip() {
+    generic_initial_solution(scip);
+}
@@ -0,0 +1,64 @@
+use float_eq::assert_float_eq;
+
+use good_lp::{
+    constraint, linearization, variables, ResolutionError, Solution, Solver, SolverModel,
+};
+
+#[allow(dead_code)]
+fn generic_linearization<S>(solver: S)
+where
+    S: Solver,
+    S::Model: SolverModel<Error = ResolutionError>,
+{
+    variables! {vars: 0 <= x <= 10; 0 <= y <= 10; a (binary); b (binary);}
+    let (largest, mut constraints) =
+        linearization::max(&mut vars, [x.into(), y.into(), 3 * a]).unwrap();
+    let (smallest, min_constraints) = linearization::min(&mut vars, [x, y]).unwrap();
+    let (both, and_constraints) = linearization::and(&mut vars, a, b).unwrap();
+    constraints.extend(min_constraints);
+    constraints.extend(and_constraints);
+    // Push the largest value down and the smallest one up
+    let mut model = vars
+        .minimise(largest - smallest + 5 * both)
+        .using(solver)
+        .with(constraint!(x + y == 9))
+        .with(constraint!(a + b == 2));
+    for constraint in constraints {
+        model.add_constraint(constraint);
+    }
+    let solution = model.solve().unwrap();
+    assert_float_eq!(solution.value(both), 1., abs <= 1e-6);
+    // max(x, y, 3) - min(x, y) is smallest when x = y
+    assert_float_eq!(solution.value(largest), 4.5, abs <= 1e-6);
+    assert_float_eq!(solution.value(smallest), 4.5, abs <= 1e-6);
+}
+
+#[cfg(feature = "coin_cbc")]
+#[test]
+fn linearization_coin_cbc() {
+    generic_linearization(good_lp::coin_cbc);
+}
+
+#[cfg(feature = "highs")]
+#[test]
+fn linearization_highs() {
+    generic_linearization(good_lp::highs);
+}
+
+#[cfg(feature = "lpsolve")]
+#[test]
+fn linearization_lpsolve() {
+    generic_linearization(good_lp::lp_solve);
+}
+
+#[cfg(feature = "minilp")]
+#[test]
+fn linearization_minilp() {
+    generic_linearization(good_lp::minilp);
+}
+
+#[cfg(feature = "scip")]
+#[test]
+fn linearization_scip() {
+    generic_linearization(good_lp::scip);
+}
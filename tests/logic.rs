@@ -0,0 +1,78 @@
+use float_eq::assert_float_eq;
+
+use good_lp::logic::{at_least, equivalent, exactly, implies, not, xor, Formula};
+use good_lp::{constraint, variables, ResolutionError, Solution, Solver, SolverModel};
+
+/// Checks the truth value of formulas over three variables for all their values
+#[allow(dead_code)]
+fn generic_truth_tables<S>(mut solver: S)
+where
+    S: Solver,
+    S::Model: SolverModel<Error = ResolutionError>,
+{
+    type Rule = fn(bool, bool, bool) -> bool;
+    for values in 0..8 {
+        let [va, vb, vc] = [values & 1 != 0, values & 2 != 0, values & 4 != 0];
+        variables! {vars: a (binary); b (binary); c (binary);}
+        let formulas: Vec<(Formula, Rule)> = vec![
+            (xor(a, b) | c, |a, b, c| (a ^ b) || c),
+            (equivalent(a, not(b)) & c, |a, b, c| a != b && c),
+            (implies(a, b) ^ c, |a, b, c| (!a || b) ^ c),
+            (at_least(2, [a, b, c]), |a, b, c| {
+                a as u8 + b as u8 + c as u8 >= 2
+            }),
+            (not(exactly(1, [a, b, c])), |a, b, c| {
+                a as u8 + b as u8 + c as u8 != 1
+            }),
+        ];
+        let mut constraints = vec![];
+        let mut expressions = vec![];
+        for (formula, _) in &formulas {
+            let (expression, formula_constraints) = formula.to_expression(&mut vars).unwrap();
+            expressions.push(expression);
+            constraints.extend(formula_constraints);
+        }
+        let mut model = solver.create_model(vars.minimise(a));
+        for constraint in constraints {
+            model.add_constraint(constraint);
+        }
+        model.add_constraint(constraint!(a == va as i32));
+        model.add_constraint(constraint!(b == vb as i32));
+        model.add_constraint(constraint!(c == vc as i32));
+        let solution = model.solve().unwrap();
+        for ((_, rule), expression) in formulas.iter().zip(expressions) {
+            let expected = rule(va, vb, vc) as i32 as f64;
+            assert_float_eq!(solution.eval(expression), expected, abs <= 1e-6);
+        }
+    }
+}
+
+#[cfg(feature = "coin_cbc")]
+#[test]
+fn truth_tables_coin_cbc() {
+    generic_truth_tables(good_lp::coin_cbc);
+}
+
+#[cfg(feature = "highs")]
+#[test]
+fn truth_tables_highs() {
+    generic_truth_tables(good_lp::highs);
+}
+
+#[cfg(feature = "lpsolve")]
+#[test]
+fn truth_tables_lpsolve() {
+    generic_truth_tables(good_lp::lp_solve);
+}
+
+#[cfg(feature = "minilp")]
+#[test]
+fn truth_tables_minilp() {
+    generic_truth_tables(good_lp::minilp);
+}
+
+#[cfg(feature = "scip")]
+#[test]
+fn truth_tables_scip() {
+    generic_truth_tables(good_lp::scip);
+}
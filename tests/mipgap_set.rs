@@ -9,6 +9,9 @@ use good_lp::coin_cbc;
 #[cfg(feature = "highs")]
 use good_lp::highs;
 
+#[cfg(feature = "minilp")]
+use good_lp::minilp;
+
 #[cfg(feature = "lp-solvers")]
 use good_lp::{solvers::lp_solvers::GlpkSolver, LpSolver};
 
@@ -45,6 +48,12 @@ fn mipgap_set_highs() {
     generic_mipgap_set(highs);
 }
 
+#[cfg(feature = "minilp")]
+#[test]
+fn mipgap_set_minilp() {
+    generic_mipgap_set(minilp);
+}
+
 #[cfg(feature = "lp-solvers")]
 #[test]
 fn mipgap_set_lp_solvers() {
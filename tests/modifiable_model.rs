@@ -0,0 +1,158 @@
+use float_eq::assert_float_eq;
+use good_lp::{
+    constraint, variable, variables, ModifiableModel, Solution, Solver, SolverModel, Variable,
+};
+
+#[cfg(feature = "coin_cbc")]
+use good_lp::coin_cbc;
+
+#[cfg(feature = "highs")]
+use good_lp::highs;
+
+#[cfg(feature = "lpsolve")]
+use good_lp::lp_solve;
+
+#[cfg(feature = "minilp")]
+use good_lp::minilp;
+
+#[allow(dead_code)]
+fn assert_values(solution: &impl Solution, expected: &[(Variable, f64)]) {
+    for &(variable, value) in expected {
+        assert_float_eq!(solution.value(variable), value, abs <= 1e-6);
+    }
+}
+
+#[allow(dead_code)]
+fn generic_modifications<S>(solver: S)
+where
+    S: Solver,
+    S::Model: ModifiableModel,
+{
+    let mut vars = variables!();
+    let a = vars.add(variable().integer().clamp(0, 10));
+    let b = vars.add(variable().clamp(0, 10));
+    let mut model = vars.maximise(2 * a + b).using(solver);
+    let sum = model.add_constraint(constraint!(a + b <= 5.5));
+    let min_b = model.add_constraint(constraint!(b >= 1));
+
+    let solution = model.resolve().unwrap();
+    assert_values(&solution, &[(a, 4.), (b, 1.5)]);
+
+    model.set_constraint_bounds(&sum, f64::NEG_INFINITY, 3.5);
+    let solution = model.resolve().unwrap();
+    assert_values(&solution, &[(a, 2.), (b, 1.5)]);
+
+    model.set_objective_coefficient(a, 0.5);
+    let solution = model.resolve().unwrap();
+    assert_values(&solution, &[(a, 0.), (b, 3.5)]);
+
+    model.remove_constraint(&min_b);
+    model.set_variable_bounds(b, 0., 2.);
+    model.set_objective_coefficient(b, -1.);
+    let solution = model.resolve().unwrap();
+    assert_values(&solution, &[(a, 3.), (b, 0.)]);
+
+    // The model can still be consumed by a last resolution
+    model.add_constraint(constraint!(a <= 1));
+    let solution = model.solve().unwrap();
+    assert_values(&solution, &[(a, 1.), (b, 0.)]);
+}
+
+#[allow(dead_code)]
+fn generic_change_after_removal<S>(solver: S)
+where
+    S: Solver,
+    S::Model: ModifiableModel,
+{
+    let mut vars = variables!();
+    let x = vars.add(variable().clamp(0, 10));
+    let y = vars.add(variable().clamp(0, 10));
+    let mut model = vars.maximise(x + y).using(solver);
+    let max_x = model.add_constraint(constraint!(x <= 2));
+    let max_y = model.add_constraint(constraint!(y <= 3));
+    let solution = model.resolve().unwrap();
+    assert_values(&solution, &[(x, 2.), (y, 3.)]);
+
+    // The constraints after a removed one can still be changed
+    model.remove_constraint(&max_x);
+    model.set_constraint_bounds(&max_y, f64::NEG_INFINITY, 1.);
+    let solution = model.resolve().unwrap();
+    assert_values(&solution, &[(x, 10.), (y, 1.)]);
+
+    let sum = model.add_constraint(constraint!(x + y <= 4));
+    model.set_constraint_bounds(&max_y, 2., 2.);
+    let solution = model.resolve().unwrap();
+    assert_values(&solution, &[(x, 2.), (y, 2.)]);
+
+    // Only the lower bound is left
+    model.set_constraint_bounds(&sum, 12., f64::INFINITY);
+    model.set_objective_coefficient(x, -1.);
+    let solution = model.resolve().unwrap();
+    assert_values(&solution, &[(x, 10.), (y, 2.)]);
+}
+
+#[cfg(feature = "coin_cbc")]
+#[test]
+fn modifications_coin_cbc() {
+    generic_modifications(coin_cbc);
+}
+
+#[cfg(feature = "highs")]
+#[test]
+fn modifications_highs() {
+    generic_modifications(highs);
+}
+
+#[cfg(feature = "minilp")]
+#[test]
+fn modifications_minilp() {
+    generic_modifications(minilp);
+}
+
+#[cfg(feature = "lpsolve")]
+#[test]
+fn modifications_lpsolve() {
+    generic_modifications(lp_solve);
+}
+
+#[cfg(feature = "coin_cbc")]
+#[test]
+fn change_after_removal_coin_cbc() {
+    generic_change_after_removal(coin_cbc);
+}
+
+#[cfg(feature = "highs")]
+#[test]
+fn change_after_removal_highs() {
+    generic_change_after_removal(highs);
+}
+
+#[cfg(feature = "lpsolve")]
+#[test]
+fn change_after_removal_lpsolve() {
+    generic_change_after_removal(lp_solve);
+}
+
+#[cfg(feature = "minilp")]
+#[test]
+fn change_after_removal_minilp() {
+    generic_change_after_removal(minilp);
+}
+
+/// The free row that lp_solve needs for SOS constraints is only added once
+#[cfg(feature = "lpsolve")]
+#[test]
+fn sos_between_resolutions_lpsolve() {
+    use good_lp::ModelWithSOS1;
+    let mut vars = variables!();
+    let x = vars.add(variable().integer().clamp(0, 2));
+    let y = vars.add(variable().integer().clamp(0, 3));
+    let mut model = vars.maximise(x + y).using(lp_solve).with_sos1(x + 2 * y);
+    assert_values(&model.resolve().unwrap(), &[(x, 0.), (y, 3.)]);
+    let constraint = model.add_constraint(constraint!(y <= 2));
+    model.set_constraint_bounds(&constraint, f64::NEG_INFINITY, 1.);
+    assert_values(&model.resolve().unwrap(), &[(x, 2.), (y, 0.)]);
+    let solution = model.resolve().unwrap();
+    assert_values(&solution, &[(x, 2.), (y, 0.)]);
+    assert_eq!(solution.into_inner().num_rows(), 2);
+}
@@ -0,0 +1,82 @@
+use float_eq::assert_float_eq;
+
+use good_lp::solvers::ObjectiveDirection::{Maximisation, Minimisation};
+use good_lp::{
+    constraint, variables, MultiObjectiveMode, ResolutionError, Solution, Solver, SolverModel,
+};
+
+/// Solves a problem where the cost `x + y` conflicts with the maximisation of `x`,
+/// and returns the optimal values of x and y
+#[allow(dead_code)]
+fn solve<S>(solver: S, mode: MultiObjectiveMode, cost: (f64, f64), x_weight: f64) -> (f64, f64)
+where
+    S: Solver,
+    S::Model: SolverModel<Error = ResolutionError>,
+{
+    variables! {vars: 0 <= x <= 10; 5 <= y <= 10;}
+    let (cost_priority, cost_tolerance) = cost;
+    // The objectives are not given in the order of their priorities
+    let objectives = [
+        (x.into(), Maximisation, x_weight, 0.),
+        (x + y, Minimisation, cost_priority, cost_tolerance),
+    ];
+    let solution = vars
+        .optimise_multiple(mode, objectives)
+        .with(constraint!(x + y >= 10))
+        .solve(solver)
+        .unwrap();
+    (solution.value(x), solution.value(y))
+}
+
+#[allow(dead_code)]
+fn generic_multi_objective<S>(solver: S)
+where
+    S: Solver + Copy,
+    S::Model: SolverModel<Error = ResolutionError>,
+{
+    use MultiObjectiveMode::*;
+    let (x, y) = solve(solver, Lexicographic, (2., 0.), 1.);
+    assert_float_eq!((x, y), (5., 5.), abs <= (1e-6, 1e-6));
+    // The cost can degrade by 2 to increase x
+    let (x, y) = solve(solver, Lexicographic, (2., 2.), 1.);
+    assert_float_eq!((x, y), (7., 5.), abs <= (1e-6, 1e-6));
+    // x matters most
+    let (x, y) = solve(solver, Lexicographic, (2., 0.), 3.);
+    assert_float_eq!((x, y), (10., 5.), abs <= (1e-6, 1e-6));
+    // minimises y - x
+    let (x, y) = solve(solver, Weighted, (1., 0.), 2.);
+    assert_float_eq!((x, y), (10., 5.), abs <= (1e-6, 1e-6));
+    // minimises x + 3y
+    let (x, y) = solve(solver, Weighted, (3., 0.), 2.);
+    assert_float_eq!((x, y), (5., 5.), abs <= (1e-6, 1e-6));
+}
+
+#[cfg(feature = "coin_cbc")]
+#[test]
+fn multi_objective_coin_cbc() {
+    generic_multi_objective(good_lp::coin_cbc);
+}
+
+#[cfg(feature = "highs")]
+#[test]
+fn multi_objective_highs() {
+    generic_multi_objective(good_lp::highs);
+}
+
+#[cfg(feature = "lpsolve")]
+#[test]
+fn multi_objective_lpsolve() {
+    generic_multi_objective(good_lp::lp_solve);
+}
+
+#[cfg(feature = "minilp")]
+#[test]
+fn multi_objective_minilp() {
+    generic_multi_objective(good_lp::minilp);
+}
+
+#[cfg(feature = "scip")]
+#[test]
+fn multi_objective_scip() {
+    generic_multi_objective(good_lp::scip);
+}
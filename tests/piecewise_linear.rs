@@ -0,0 +1,73 @@
+use float_eq::assert_float_eq;
+
+use good_lp::{
+    constraint, variables, ModelWithSOS2, ResolutionError, Solution, Solver, SolverModel,
+};
+
+/// Buying in bulk is cheaper: the cost is a concave function of the quantity,
+/// so without the SOS2 condition the weights of the first and last breakpoints would be used
+const BULK_PRICES: [(f64, f64); 3] = [(0., 0.), (10., 20.), (20., 25.)];
+
+#[allow(dead_code)]
+fn generic_piecewise_linear_binary<S>(solver: S)
+where
+    S: Solver,
+    S::Model: SolverModel<Error = ResolutionError>,
+{
+    variables! {vars: 0 <= quantity <= 20;}
+    let cost = vars.add_piecewise_linear(quantity, &BULK_PRICES);
+    let constraints = cost.binary_constraints(&mut vars);
+    let mut model = vars.minimise(cost.value()).using(solver);
+    for constraint in constraints {
+        model.add_constraint(constraint);
+    }
+    model.add_constraint(constraint!(quantity == 15));
+    let solution = model.solve().unwrap();
+    assert_float_eq!(solution.eval(cost.value()), 22.5, abs <= 1e-6);
+}
+
+#[allow(dead_code)]
+fn generic_piecewise_linear_sos2<S>(solver: S)
+where
+    S: Solver,
+    S::Model: SolverModel<Error = ResolutionError> + ModelWithSOS2,
+{
+    variables! {vars: 0 <= quantity <= 20;}
+    let cost = vars.add_piecewise_linear(quantity, &BULK_PRICES);
+    let mut model = vars.minimise(cost.value()).using(solver);
+    cost.add_sos2_constraints(&mut model);
+    model.add_constraint(constraint!(quantity == 15));
+    let solution = model.solve().unwrap();
+    assert_float_eq!(solution.eval(cost.value()), 22.5, abs <= 1e-6);
+}
+
+#[cfg(feature = "coin_cbc")]
+#[test]
+fn piecewise_linear_coin_cbc() {
+    generic_piecewise_linear_binary(good_lp::coin_cbc);
+}
+
+#[cfg(feature = "highs")]
+#[test]
+fn piecewise_linear_highs() {
+    generic_piecewise_linear_binary(good_lp::highs);
+}
+
+#[cfg(feature = "lpsolve")]
+#[test]
+fn piecewise_linear_lpsolve() {
+    generic_piecewise_linear_binary(good_lp::lp_solve);
+    generic_piecewise_linear_sos2(good_lp::lp_solve);
+}
+
+#[cfg(feature = "minilp")]
+#[test]
+fn piecewise_linear_minilp() {
+    generic_piecewise_linear_binary(good_lp::minilp);
+}
+
+#[cfg(feature = "scip")]
+#[test]
+fn piecewise_linear_scip() {
+    generic_piecewise_linear_binary(good_lp::scip);
+}
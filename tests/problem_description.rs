@@ -0,0 +1,56 @@
+use std::collections::HashMap;
+
+use float_eq::assert_float_eq;
+use good_lp::{
+    constraint, default_solver, variable, variables, ProblemDescription, Solution, SolverModel,
+};
+
+fn knapsack() -> (ProblemDescription, Vec<good_lp::Variable>) {
+    let mut vars = variables!();
+    let items = vars.add_vector(variable().clamp(0, 1), 3);
+    let problem = vars
+        .maximise(5 * items[0] + 4 * items[1] + 3 * items[2])
+        .with(constraint!(2 * items[0] + 3 * items[1] + items[2] <= 5))
+        .with(constraint!(4 * items[0] + items[1] + 2 * items[2] <= 11));
+    (problem, items)
+}
+
+#[test]
+fn inspect_constraints() {
+    let (problem, items) = knapsack();
+    assert_eq!(problem.variables().len(), 3);
+    assert_eq!(problem.constraints().len(), 2);
+    let references: Vec<_> = problem.iter_constraints().map(|(r, _)| r).collect();
+    let first = problem
+        .variables()
+        .display(problem.constraint(&references[0]));
+    assert!(first.to_string().ends_with("<= 5"), "{}", first);
+    let values: HashMap<_, _> = vec![(items[0], 1.), (items[1], 1.), (items[2], 0.)]
+        .into_iter()
+        .collect();
+    assert_float_eq!(problem.objective().eval_with(&values), 9., abs <= 1e-10);
+}
+
+#[test]
+fn solve_the_same_description_twice() {
+    let (problem, items) = knapsack();
+    let first = problem.clone().using(default_solver).solve().unwrap();
+    let mut second_problem = problem;
+    second_problem.add_constraint(constraint!(items[0] <= 0.5));
+    let second = second_problem.using(default_solver).solve().unwrap();
+    assert_float_eq!(first.value(items[0]), 1., abs <= 1e-6);
+    assert_float_eq!(second.value(items[0]), 0.5, abs <= 1e-6);
+}
+
+#[test]
+fn constraint_names() {
+    let (mut problem, items) = knapsack();
+    let named = problem.add_constraint(constraint!(items[0] + items[2] <= 1).named("exclusive"));
+    assert_eq!(named.name(), Some("exclusive"));
+    let names: Vec<_> = problem
+        .iter_constraints()
+        .map(|(reference, _)| reference.name().map(String::from))
+        .collect();
+    assert_eq!(names, [None, None, Some("exclusive".to_string())]);
+    assert_eq!(problem.constraint(&named).name(), Some("exclusive"));
+}
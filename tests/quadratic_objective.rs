@@ -0,0 +1,100 @@
+use float_eq::assert_float_eq;
+
+use good_lp::{constraint, variables, ResolutionError, Solution, Solver, SolverModel};
+
+#[allow(dead_code)]
+fn generic_quadratic_objective<S>(solver: S)
+where
+    S: Solver,
+    S::Model: SolverModel<Error = ResolutionError>,
+{
+    variables! {vars: x; 0 <= y <= 10;}
+    // The point of the line x + y = 4 that is the closest to (1, 2)
+    let solution = vars
+        .minimise((x - 1) * (x - 1) + (y - 2) * (y - 2) + 1)
+        .using(solver)
+        .with(constraint!(x + y == 4))
+        .solve()
+        .unwrap();
+    assert_float_eq!(solution.value(x), 1.5, abs <= 1e-5);
+    assert_float_eq!(solution.value(y), 2.5, abs <= 1e-5);
+}
+
+#[allow(dead_code)]
+fn generic_quadratic_objective_maximisation<S>(solver: S)
+where
+    S: Solver,
+    S::Model: SolverModel<Error = ResolutionError>,
+{
+    variables! {vars: x; y;}
+    // The gradient -2x - y + 3, -x - 2y + 3 is zero at (1, 1)
+    let objective = 3 * x + 3 * y - x * x - x * y - y * y + 2;
+    let solution = vars
+        .maximise(objective.clone())
+        .using(solver)
+        .solve()
+        .unwrap();
+    assert_float_eq!(solution.value(x), 1., abs <= 1e-5);
+    assert_float_eq!(solution.value(y), 1., abs <= 1e-5);
+    assert_float_eq!(objective.eval_with(&solution), 5., abs <= 1e-5);
+}
+
+#[allow(dead_code)]
+fn generic_unsupported_quadratic_objective<S>(solver: S)
+where
+    S: Solver,
+    S::Model: SolverModel<Error = ResolutionError>,
+{
+    variables! {vars: 0 <= x <= 1;}
+    let result = vars.maximise(x * x).using(solver).solve();
+    assert!(matches!(result, Err(ResolutionError::Other(_))));
+}
+
+#[cfg(feature = "scip")]
+#[test]
+fn quadratic_objective_scip() {
+    generic_quadratic_objective(good_lp::scip);
+    generic_quadratic_objective_maximisation(good_lp::scip);
+}
+
+#[cfg(feature = "highs")]
+#[test]
+fn quadratic_objective_highs() {
+    use good_lp::SolutionInfo;
+    generic_quadratic_objective(good_lp::highs);
+    generic_quadratic_objective_maximisation(good_lp::highs);
+    // The objective value includes the quadratic part and the constant
+    variables! {vars: x;}
+    let solution = vars
+        .minimise(x * x - 2 * x + 3)
+        .using(good_lp::highs)
+        .solve()
+        .unwrap();
+    assert_float_eq!(solution.objective_value(), 2., abs <= 1e-5);
+}
+
+#[cfg(feature = "highs")]
+#[test]
+fn unsupported_integer_quadratic_objective_highs() {
+    variables! {vars: 0 <= x (integer) <= 10;}
+    let result = vars.minimise(x * x - 3 * x).using(good_lp::highs).solve();
+    assert!(matches!(result, Err(ResolutionError::Other(_))));
+}
+
+#[cfg(feature = "coin_cbc")]
+#[test]
+fn unsupported_quadratic_objective_coin_cbc() {
+    generic_unsupported_quadratic_objective(good_lp::coin_cbc);
+}
+
+#[cfg(feature = "minilp")]
+#[test]
+fn unsupported_quadratic_objective_minilp() {
+    generic_unsupported_quadratic_objective(good_lp::minilp);
+}
+
+#[cfg(feature = "lpsolve")]
+#[test]
+fn unsupported_quadratic_objective_lpsolve() {
+    generic_unsupported_quadratic_objective(good_lp::lp_solve);
+}
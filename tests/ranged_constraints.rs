@@ -0,0 +1,73 @@
+use good_lp::{constraint, variables, Solution, Solver, SolverModel};
+
+#[cfg(feature = "coin_cbc")]
+use good_lp::coin_cbc;
+
+#[cfg(feature = "highs")]
+use good_lp::highs;
+
+#[cfg(feature = "minilp")]
+use good_lp::minilp;
+
+#[cfg(feature = "lpsolve")]
+use good_lp::lp_solve;
+
+#[cfg(feature = "scip")]
+use good_lp::scip;
+
+#[allow(dead_code)]
+fn generic_ranged<S: Solver + Copy>(solver: S) {
+    variables! { vars: a; b; };
+    let solution = vars
+        .clone()
+        .maximise(a - b)
+        .using(solver)
+        .with(constraint!(-3 <= a + b <= 5))
+        .with(constraint!(2 >= a - 2 * b + 1 >= -7))
+        .solve()
+        .unwrap();
+    // a - 2b <= 1 and a + b <= 5: a = 11/3, b = 4/3
+    assert!((solution.value(a) - 11. / 3.).abs() < 1e-6);
+    assert!((solution.value(b) - 4. / 3.).abs() < 1e-6);
+
+    let solution = vars
+        .minimise(a - b)
+        .using(solver)
+        .with(constraint!(-3 <= a + b <= 5))
+        .with(constraint!(2 >= a - 2 * b + 1 >= -7))
+        .solve()
+        .unwrap();
+    // a - 2b >= -8 and a + b >= -3: a = -14/3, b = 5/3
+    assert!((solution.value(a) + 14. / 3.).abs() < 1e-6);
+    assert!((solution.value(b) - 5. / 3.).abs() < 1e-6);
+}
+
+#[cfg(feature = "coin_cbc")]
+#[test]
+fn ranged_coin_cbc() {
+    generic_ranged(coin_cbc);
+}
+
+#[cfg(feature = "highs")]
+#[test]
+fn ranged_highs() {
+    generic_ranged(highs);
+}
+
+#[cfg(feature = "minilp")]
+#[test]
+fn ranged_minilp() {
+    generic_ranged(minilp);
+}
+
+#[cfg(feature = "lpsolve")]
+#[test]
+fn ranged_lpsolve() {
+    generic_ranged(lp_solve);
+}
+
+#[cfg(feature = "scip")]
+#[test]
+fn ranged_scip() {
+    generic_ranged(scip);
+}
@@ -0,0 +1,65 @@
+use float_eq::assert_float_eq;
+
+use good_lp::{constraint, variables, Solution, SolutionWithReducedCosts, Solver, SolverModel};
+
+#[allow(dead_code)]
+fn generic_reduced_costs<S>(solver: S)
+where
+    S: Solver,
+    <S::Model as SolverModel>::Solution: SolutionWithReducedCosts,
+{
+    variables! {vars: 0 <= x; 0 <= y <= 10; 1 <= z <= 3;}
+    let solution = vars
+        .minimise(2 * x + 3 * y + z)
+        .using(solver)
+        .with(constraint!(x + y + z >= 4))
+        .solve()
+        .unwrap();
+    assert_float_eq!(solution.value(x), 1., abs <= 1e-6);
+    assert_float_eq!(solution.value(y), 0., abs <= 1e-6);
+    assert_float_eq!(solution.value(z), 3., abs <= 1e-6);
+    // x is between its bounds
+    assert_float_eq!(solution.reduced_cost(x), 0., abs <= 1e-6);
+    // Increasing y or z by 1 allows decreasing x by 1
+    assert_float_eq!(solution.reduced_cost(y), 1., abs <= 1e-6);
+    assert_float_eq!(solution.reduced_cost(z), -1., abs <= 1e-6);
+}
+
+#[allow(dead_code)]
+fn generic_reduced_costs_maximisation<S>(solver: S)
+where
+    S: Solver,
+    <S::Model as SolverModel>::Solution: SolutionWithReducedCosts,
+{
+    variables! {vars: 0 <= x; 0 <= y <= 10; 1 <= z <= 3;}
+    let solution = vars
+        .maximise(-2 * x - 3 * y - z)
+        .using(solver)
+        .with(constraint!(x + y + z >= 4))
+        .solve()
+        .unwrap();
+    assert_float_eq!(solution.value(x), 1., abs <= 1e-6);
+    // Increasing y by 1 decreases the objective by 1
+    assert_float_eq!(solution.reduced_cost(y), -1., abs <= 1e-6);
+    assert_float_eq!(solution.reduced_cost(z), 1., abs <= 1e-6);
+}
+
+#[cfg(all(feature = "coin_cbc", feature = "cbc-310"))]
+#[test]
+fn reduced_costs_coin_cbc() {
+    generic_reduced_costs(good_lp::coin_cbc);
+}
+
+#[cfg(feature = "highs")]
+#[test]
+fn reduced_costs_highs() {
+    generic_reduced_costs(good_lp::highs);
+    generic_reduced_costs_maximisation(good_lp::highs);
+}
+
+#[cfg(feature = "lpsolve")]
+#[test]
+fn reduced_costs_lpsolve() {
+    generic_reduced_costs(good_lp::lp_solve);
+    generic_reduced_costs_maximisation(good_lp::lp_solve);
+}
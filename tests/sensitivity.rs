@@ -0,0 +1,103 @@
+use float_eq::assert_float_eq;
+
+use good_lp::{
+    constraint, variables, SensitivityRange, Solution, SolutionWithSensitivity, Solver, SolverModel,
+};
+
+#[cfg(feature = "highs")]
+use good_lp::highs;
+#[cfg(feature = "lpsolve")]
+use good_lp::lp_solve;
+#[cfg(feature = "minilp")]
+use good_lp::minilp;
+
+fn assert_range(range: SensitivityRange, allowable_decrease: f64, allowable_increase: f64) {
+    assert_float_eq!(range.allowable_decrease, allowable_decrease, abs <= 1e-6);
+    assert_float_eq!(range.allowable_increase, allowable_increase, abs <= 1e-6);
+}
+
+#[allow(dead_code)]
+fn generic_sensitivity<S>(solver: S)
+where
+    S: Solver,
+    <S::Model as SolverModel>::Solution: SolutionWithSensitivity,
+{
+    variables! {vars: 0 <= x; 0 <= y; 0 <= z;}
+    let mut model = vars.minimise(2 * x + 3 * y + 5 * z).using(solver);
+    // Stored as -x - y - z <= -4
+    let demand = model.add_constraint(constraint!(x + y + z >= 4));
+    let max_x = model.add_constraint(constraint!(x <= 3));
+    // Stored as -x <= -1
+    let min_x = model.add_constraint(constraint!(x >= 1));
+    let solution = model.solve().unwrap();
+    assert_float_eq!(solution.value(x), 3., abs <= 1e-6);
+    assert_float_eq!(solution.value(y), 1., abs <= 1e-6);
+    assert_float_eq!(solution.value(z), 0., abs <= 1e-6);
+
+    let report = solution.sensitivity_report();
+    // x stays at its maximum as long as it is cheaper than y
+    assert_range(report.objective_coefficient(x), f64::INFINITY, 1.);
+    // y completes the demand as long as it is cheaper than x and z
+    assert_range(report.objective_coefficient(y), 1., 2.);
+    // z is not used until it is cheaper than y
+    assert_range(report.objective_coefficient(z), 2., f64::INFINITY);
+    // The demand can increase indefinitely, and decrease until y is 0
+    assert_range(report.constraint_bound(&demand), f64::INFINITY, 1.);
+    assert_range(report.constraint_bound(&max_x), 2., 1.);
+    // The minimum of x is not reached, and can increase up to 3
+    assert_range(report.constraint_bound(&min_x), 2., f64::INFINITY);
+}
+
+#[allow(dead_code)]
+fn generic_sensitivity_maximisation<S>(solver: S)
+where
+    S: Solver,
+    <S::Model as SolverModel>::Solution: SolutionWithSensitivity,
+{
+    variables! {vars: 0 <= x; 0 <= y;}
+    let mut model = vars.maximise(3 * x + 2 * y).using(solver);
+    let total = model.add_constraint(constraint!(x + y <= 4));
+    let solution = model.solve().unwrap();
+    assert_float_eq!(solution.value(x), 4., abs <= 1e-6);
+
+    let report = solution.sensitivity_report();
+    // x is used as long as it is more profitable than y
+    assert_range(report.objective_coefficient(x), 1., f64::INFINITY);
+    assert_range(report.objective_coefficient(y), f64::INFINITY, 1.);
+    assert_range(report.constraint_bound(&total), 4., f64::INFINITY);
+}
+
+#[cfg(feature = "minilp")]
+#[test]
+fn sensitivity_minilp() {
+    generic_sensitivity(minilp);
+    generic_sensitivity_maximisation(minilp);
+}
+
+#[cfg(feature = "highs")]
+#[test]
+fn sensitivity_highs() {
+    generic_sensitivity(|problem| highs(problem).set_ranging(true));
+    generic_sensitivity_maximisation(|problem| highs(problem).set_ranging(true));
+}
+
+#[cfg(feature = "highs")]
+#[test]
+fn sensitivity_highs_without_ranging() {
+    variables! {vars: 0 <= x;}
+    let solution = vars
+        .maximise(x)
+        .using(highs)
+        .with(constraint!(x <= 1))
+        .solve()
+        .unwrap();
+    let report = solution.sensitivity_report();
+    assert!(report.objective_coefficient(x).allowable_decrease.is_nan());
+}
+
+#[cfg(feature = "lpsolve")]
+#[test]
+fn sensitivity_lpsolve() {
+    generic_sensitivity(lp_solve);
+    generic_sensitivity_maximisation(lp_solve);
+}
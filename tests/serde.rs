@@ -0,0 +1,96 @@
+#![cfg(feature = "serde")]
+
+use good_lp::variable::UnsolvedProblem;
+use good_lp::{
+    constraint, variable, variables, Constraint, Expression, ProblemDescription, ProblemVariables,
+};
+
+#[test]
+fn expressions_have_a_stable_representation() {
+    let mut vars = variables!();
+    let v = vars.add_vector(variable(), 50);
+    let expr: Expression = v.iter().rev().enumerate().map(|(i, &x)| i as f64 * x).sum();
+    let json = serde_json::to_string(&(expr + 1)).unwrap();
+    assert!(json.starts_with(r#"{"coefficients":[[0,49.0],[1,48.0],[2,47.0],"#));
+    assert!(json.ends_with(r#"[49,0.0]],"constant":1.0}"#));
+    let parsed: Expression = serde_json::from_str(&json).unwrap();
+    assert_eq!(serde_json::to_string(&parsed).unwrap(), json);
+}
+
+#[test]
+fn serialize_problem() {
+    let mut vars = variables!();
+    let x = vars.add(variable().name("x").clamp(0, 3));
+    let y = vars.add(variable().name("y").integer().min(1));
+    let problem = vars.maximise(2 * x + y).with(constraint!(x + y <= 4));
+    let json = serde_json::to_string(&problem).unwrap();
+    assert_eq!(
+        json,
+        r#"{"problem":{"objective":{"coefficients":[[0,2.0],[1,1.0]],"constant":0.0},"direction":"Maximisation","variables":[{"min":0.0,"max":3.0,"name":"x","is_integer":false},{"min":1.0,"max":null,"name":"y","is_integer":true}]},"constraints":[{"expression":{"coefficients":[[0,1.0],[1,1.0]],"constant":-4.0},"is_equality":false}]}"#
+    );
+}
+
+#[test]
+fn round_trip() {
+    let mut vars = variables!();
+    let x = vars.add(variable().name("x").clamp(-1.5, 3));
+    let y = vars.add(variable().name("y").binary());
+    vars.add(variable().name("free"));
+    let unsolved: UnsolvedProblem = vars.minimise(x - y + 2);
+    let constraint: Constraint = constraint!(3 * x == y);
+    let problem = unsolved.with(constraint.clone());
+
+    let json = serde_json::to_string(&problem).unwrap();
+    let parsed: ProblemDescription = serde_json::from_str(&json).unwrap();
+    assert_eq!(parsed.objective(), problem.objective());
+    assert_eq!(parsed.direction(), problem.direction());
+    let defs = |vars: &ProblemVariables| {
+        vars.iter_variables_with_def()
+            .map(|(_, def)| def.clone())
+            .collect::<Vec<_>>()
+    };
+    assert_eq!(defs(parsed.variables()), defs(problem.variables()));
+    assert_eq!(
+        parsed
+            .variables()
+            .display(&parsed.constraints()[0])
+            .to_string(),
+        problem.variables().display(&constraint).to_string()
+    );
+
+    let var_json = serde_json::to_string(&y).unwrap();
+    assert_eq!(var_json, "1");
+    assert_eq!(
+        serde_json::from_str::<good_lp::Variable>(&var_json).unwrap(),
+        y
+    );
+}
+
+#[test]
+fn unknown_variables_are_rejected() {
+    let problem = r#"{"objective":{"coefficients":[[0,2.0]],"constant":0.0},"direction":"Maximisation","variables":[{"min":0.0,"max":3.0,"name":"x","is_integer":false}]}"#;
+    assert!(serde_json::from_str::<UnsolvedProblem>(problem).is_ok());
+    let objective = problem.replace("[[0,2.0]]", "[[1,2.0]]");
+    let error = serde_json::from_str::<UnsolvedProblem>(&objective)
+        .err()
+        .unwrap();
+    assert!(error.to_string().contains("v1"), "{}", error);
+    let quadratic = problem.replace(
+        r#""direction""#,
+        r#""quadratic_objective":[[0,3,1.0]],"direction""#,
+    );
+    assert!(serde_json::from_str::<UnsolvedProblem>(&quadratic).is_err());
+
+    let constraint =
+        r#"{"expression":{"coefficients":[[7,1.0]],"constant":-4.0},"is_equality":false}"#;
+    let description = format!(
+        r#"{{"problem":{},"constraints":[{}]}}"#,
+        problem, constraint
+    );
+    let error = serde_json::from_str::<ProblemDescription>(&description)
+        .err()
+        .unwrap();
+    assert!(error.to_string().contains("v7"), "{}", error);
+    let invalid_problem = format!(r#"{{"problem":{},"constraints":[]}}"#, objective);
+    assert!(serde_json::from_str::<ProblemDescription>(&invalid_problem).is_err());
+}
@@ -3,7 +3,7 @@ use float_eq::assert_float_eq;
 use good_lp::{
     constraint,
     solvers::{DualValues, SolutionWithDual},
-    variable, variables, Solution, Solver, SolverModel,
+    variable, variables, ModifiableModel, Solution, Solver, SolverModel,
 };
 
 // Using a generic function here ensures the dual can be retrieved in a generic,
@@ -72,20 +72,85 @@ where
     assert_float_eq!(5.0, dual.dual(c2), abs <= 1e-1);
 }
 
+#[allow(dead_code)]
+fn minimisation_problem_for_solver<S: Solver>(solver: S)
+where
+    for<'a> <<S as Solver>::Model as SolverModel>::Solution: SolutionWithDual<'a>,
+{
+    let mut vars = variables!();
+    let x = vars.add(variable().min(0));
+    let y = vars.add(variable().min(0));
+    let mut p = vars.minimise(2 * x + 3 * y).using(solver);
+    // Stored as -x - y <= -4: the dual value is the change in the objective
+    // when the bound of -x - y increases, that is when the right-hand side decreases
+    let c1 = p.add_constraint(constraint!(x + y >= 4));
+    let c2 = p.add_constraint(constraint!(x - y == 1));
+    let c3 = p.add_constraint(constraint!(x <= 10));
+
+    let mut solution = p.solve().expect("Library test");
+    assert_float_eq!(2.5, solution.value(x), abs <= 1e-6);
+    assert_float_eq!(1.5, solution.value(y), abs <= 1e-6);
+
+    let dual = solution.compute_dual();
+    assert_float_eq!(-2.5, dual.dual(c1), abs <= 1e-6);
+    assert_float_eq!(-0.5, dual.dual(c2), abs <= 1e-6);
+    assert_float_eq!(0., dual.dual(c3), abs <= 1e-6);
+}
+
+#[allow(dead_code)]
+fn dual_after_removal_for_solver<S: Solver>(solver: S)
+where
+    S::Model: ModifiableModel,
+    for<'a> <<S as Solver>::Model as SolverModel>::Solution: SolutionWithDual<'a>,
+{
+    let mut vars = variables!();
+    let x = vars.add(variable().min(0));
+    let y = vars.add(variable().min(0));
+    let mut p = vars.maximise(3 * x + 2 * y).using(solver);
+    let c1 = p.add_constraint(constraint!(x <= 1));
+    let c2 = p.add_constraint(constraint!(x + y <= 4));
+
+    let mut solution = p.resolve().expect("Library test");
+    let dual = solution.compute_dual();
+    assert_float_eq!(1., dual.dual(c1.clone()), abs <= 1e-6);
+    assert_float_eq!(2., dual.dual(c2.clone()), abs <= 1e-6);
+
+    // The rows after a removed constraint are renumbered by the solvers
+    p.remove_constraint(&c1);
+    let mut solution = p.resolve().expect("Library test");
+    assert_float_eq!(4., solution.value(x), abs <= 1e-6);
+    let dual = solution.compute_dual();
+    assert_float_eq!(0., dual.dual(c1), abs <= 1e-6);
+    assert_float_eq!(3., dual.dual(c2), abs <= 1e-6);
+}
+
 macro_rules! dual_test {
-    ($solver_feature:literal, $solver:expr) => {
-        #[test]
+    ($solver_feature:literal, $solver:ident) => {
         #[cfg(feature = $solver_feature)]
-        fn determine_shadow_prices() {
-            determine_shadow_prices_for_solver($solver)
-        }
+        mod $solver {
+            #[test]
+            fn determine_shadow_prices() {
+                super::determine_shadow_prices_for_solver(good_lp::$solver)
+            }
 
-        #[test]
-        #[cfg(feature = $solver_feature)]
-        fn furniture_problem() {
-            furniture_problem_for_solver($solver)
+            #[test]
+            fn furniture_problem() {
+                super::furniture_problem_for_solver(good_lp::$solver)
+            }
+
+            #[test]
+            fn minimisation_problem() {
+                super::minimisation_problem_for_solver(good_lp::$solver)
+            }
+
+            #[test]
+            fn dual_after_removal() {
+                super::dual_after_removal_for_solver(good_lp::$solver)
+            }
         }
     };
 }
 
-dual_test!("highs", good_lp::highs);
+dual_test!("highs", highs);
+dual_test!("lpsolve", lp_solve);
+dual_test!("minilp", minilp);
@@ -0,0 +1,71 @@
+use float_eq::assert_float_eq;
+
+use good_lp::{
+    constraint, variables, Expression, ProblemDescription, ResolutionError, Solver, SolverModel,
+};
+
+#[allow(dead_code)]
+fn generic_soft_constraint<S>(solver: S)
+where
+    S: Solver + Copy,
+    S::Model: SolverModel<Error = ResolutionError>,
+{
+    for &(penalty, expected_x) in &[(2., 4.), (0.5, 10.)] {
+        variables! {vars: 0 <= x <= 10;}
+        let mut problem = ProblemDescription::new(vars.maximise(x));
+        let goal = problem.add_soft_constraint(constraint!(x <= 4).soft(penalty));
+        let solution = problem.using(solver).solve().unwrap();
+        assert_float_eq!(goal.violation(&solution), expected_x - 4., abs <= 1e-6);
+    }
+}
+
+#[allow(dead_code)]
+fn generic_goal_programming<S>(solver: S)
+where
+    S: Solver + Copy,
+    S::Model: SolverModel<Error = ResolutionError>,
+{
+    variables! {vars: 0 <= x; 0 <= y;}
+    let mut problem = ProblemDescription::new(vars.minimise(Expression::from(0)));
+    problem.add_constraint(constraint!(x + y == 10));
+    let x_goal = problem.add_soft_constraint(constraint!(x == 2).soft(1.));
+    let y_goal = problem.add_soft_constraint(constraint!(2 <= y <= 3).soft(3.));
+    let solution = problem.using(solver).solve().unwrap();
+    assert_float_eq!(x_goal.violation(&solution), 5., abs <= 1e-6);
+    assert_float_eq!(y_goal.violation(&solution), 0., abs <= 1e-6);
+}
+
+#[cfg(feature = "coin_cbc")]
+#[test]
+fn soft_constraint_coin_cbc() {
+    generic_soft_constraint(good_lp::coin_cbc);
+    generic_goal_programming(good_lp::coin_cbc);
+}
+
+#[cfg(feature = "highs")]
+#[test]
+fn soft_constraint_highs() {
+    generic_soft_constraint(good_lp::highs);
+    generic_goal_programming(good_lp::highs);
+}
+
+#[cfg(feature = "lpsolve")]
+#[test]
+fn soft_constraint_lpsolve() {
+    generic_soft_constraint(good_lp::lp_solve);
+    generic_goal_programming(good_lp::lp_solve);
+}
+
+#[cfg(feature = "minilp")]
+#[test]
+fn soft_constraint_minilp() {
+    generic_soft_constraint(good_lp::minilp);
+    generic_goal_programming(good_lp::minilp);
+}
+
+#[cfg(feature = "scip")]
+#[test]
+fn soft_constraint_scip() {
+    generic_soft_constraint(good_lp::scip);
+    generic_goal_programming(good_lp::scip);
+}
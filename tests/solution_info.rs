@@ -0,0 +1,67 @@
+use good_lp::{constraint, variables, Solution, SolutionInfo, SolutionStatus, Solver, SolverModel};
+
+#[cfg(feature = "coin_cbc")]
+use good_lp::coin_cbc;
+
+#[cfg(feature = "highs")]
+use good_lp::highs;
+
+#[cfg(feature = "minilp")]
+use good_lp::minilp;
+
+#[cfg(feature = "lpsolve")]
+use good_lp::lp_solve;
+
+#[cfg(feature = "scip")]
+use good_lp::scip;
+
+#[allow(dead_code)]
+fn generic_solution_info<S>(solver: S)
+where
+    S: Solver,
+    <S::Model as SolverModel>::Solution: SolutionInfo,
+{
+    variables! { vars: 0 <= a <= 10; 0 <= b (integer) <= 10; };
+    let solution = vars
+        .maximise(3 * a + 2 * b - 5)
+        .using(solver)
+        .with(constraint!(a + b <= 4.5))
+        .with(constraint!(a <= 2))
+        .solve()
+        .unwrap();
+    assert_eq!(solution.status(), SolutionStatus::Optimal);
+    // a = 1.5, b = 3
+    assert!((solution.objective_value() - 5.5).abs() < 1e-6);
+    assert!(solution.best_bound() >= solution.objective_value() - 1e-6);
+    assert!(solution.gap() < 1e-4);
+}
+
+#[cfg(feature = "coin_cbc")]
+#[test]
+fn solution_info_coin_cbc() {
+    generic_solution_info(coin_cbc);
+}
+
+#[cfg(feature = "highs")]
+#[test]
+fn solution_info_highs() {
+    generic_solution_info(highs);
+}
+
+#[cfg(feature = "minilp")]
+#[test]
+fn solution_info_minilp() {
+    generic_solution_info(minilp);
+}
+
+#[cfg(feature = "lpsolve")]
+#[test]
+fn solution_info_lpsolve() {
+    generic_solution_info(lp_solve);
+}
+
+#[cfg(feature = "scip")]
+#[test]
+fn solution_info_scip() {
+    generic_solution_info(scip);
+}
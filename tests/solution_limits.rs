@@ -0,0 +1,107 @@
+use good_lp::{
+    constraint, variable, variables, Expression, Solution, SolutionStatus, Solver, SolverModel,
+    Variable, WithTimeLimit,
+};
+
+const ROWS: usize = 4;
+const COLUMNS: usize = 30;
+
+/// A market split problem (Cornuéjols and Dawande, 1998): the sum of the coefficients
+/// of the selected columns should be equal to half of the total on every row.
+/// The distance to this target is minimised. Any selection is a feasible solution,
+/// but branch and bound algorithms cannot prove the optimality of the best one quickly.
+fn market_split() -> (Vec<Vec<f64>>, Vec<f64>) {
+    // A small linear congruential generator, to always get the same coefficients
+    let mut state: u64 = 12345;
+    let mut next = || {
+        state = state
+            .wrapping_mul(6364136223846793005)
+            .wrapping_add(1442695040888963407);
+        ((state >> 33) % 100) as f64
+    };
+    let coefficients: Vec<Vec<f64>> = (0..ROWS)
+        .map(|_| (0..COLUMNS).map(|_| next()).collect())
+        .collect();
+    let targets = coefficients
+        .iter()
+        .map(|row| (row.iter().sum::<f64>() / 2.).floor())
+        .collect();
+    (coefficients, targets)
+}
+
+#[allow(dead_code)]
+fn generic_time_limit_with_incumbent<S>(solver: S) -> <S::Model as SolverModel>::Solution
+where
+    S: Solver,
+    S::Model: WithTimeLimit,
+{
+    let (coefficients, targets) = market_split();
+    let mut vars = variables!();
+    let selected = vars.add_vector(variable().binary(), COLUMNS);
+    let excess = vars.add_vector(variable().min(0), ROWS);
+    let shortfall = vars.add_vector(variable().min(0), ROWS);
+    let distance: Expression = excess.iter().chain(&shortfall).sum();
+    let mut model = vars.minimise(distance).using(solver).with_time_limit(1.);
+    let totals: Vec<Expression> = coefficients
+        .iter()
+        .map(|row| row.iter().zip(&selected).map(|(&c, &x)| c * x).sum())
+        .collect();
+    for i in 0..ROWS {
+        model.add_constraint(constraint!(
+            totals[i].clone() - excess[i] + shortfall[i] == targets[i]
+        ));
+    }
+    let solution = model
+        .solve()
+        .expect("a solution should be found before the limit");
+    assert_ne!(solution.status(), SolutionStatus::Optimal);
+    let value = |var: &Variable| solution.value(*var);
+    for x in selected.iter().map(value) {
+        assert!(
+            x.abs() < 1e-6 || (x - 1.).abs() < 1e-6,
+            "{} is not binary",
+            x
+        );
+    }
+    for i in 0..ROWS {
+        assert!(value(&excess[i]) >= -1e-6 && value(&shortfall[i]) >= -1e-6);
+        let row = solution.eval(totals[i].clone()) - value(&excess[i]) + value(&shortfall[i]);
+        assert!((row - targets[i]).abs() < 1e-6, "row {}: {}", i, row);
+    }
+    solution
+}
+
+#[cfg(feature = "coin_cbc")]
+#[test]
+fn time_limit_coin_cbc() {
+    generic_time_limit_with_incumbent(good_lp::coin_cbc);
+}
+
+#[cfg(feature = "highs")]
+#[test]
+fn time_limit_highs() {
+    generic_time_limit_with_incumbent(good_lp::highs);
+}
+
+#[cfg(feature = "lpsolve")]
+#[test]
+fn time_limit_lpsolve() {
+    generic_time_limit_with_incumbent(good_lp::lp_solve);
+}
+
+#[cfg(feature = "minilp")]
+#[test]
+fn time_limit_minilp() {
+    generic_time_limit_with_incumbent(good_lp::minilp);
+}
+
+#[cfg(feature = "scip")]
+#[test]
+fn time_limit_scip() {
+    use good_lp::SolutionInfo;
+    let solution = generic_time_limit_with_incumbent(good_lp::scip);
+    // The distance cannot be negative, and SCIP reports the bound it proved before the limit
+    let bound = solution.best_bound();
+    assert!(bound >= -1e-6 && bound <= solution.objective_value() + 1e-6);
+    assert!(solution.gap().is_finite());
+}
@@ -0,0 +1,102 @@
+use good_lp::{
+    variables, Solution, Solver, SolverModel, WithRandomSeed, WithThreads, WithTimeLimit,
+    WithVerbosity,
+};
+
+#[cfg(feature = "coin_cbc")]
+use good_lp::coin_cbc;
+
+#[cfg(feature = "highs")]
+use good_lp::highs;
+
+#[cfg(feature = "minilp")]
+use good_lp::minilp;
+
+#[cfg(feature = "lpsolve")]
+use good_lp::lp_solve;
+
+#[cfg(feature = "scip")]
+use good_lp::scip;
+
+#[allow(dead_code)]
+fn generic_time_limit<S>(solver: S)
+where
+    S: Solver,
+    S::Model: WithTimeLimit,
+{
+    variables! { vars: 0 <= a (integer) <= 10; };
+    let model = vars.maximise(a).using(solver);
+    assert_eq!(model.time_limit(), None);
+    let model = model.with_time_limit(30.);
+    assert_eq!(model.time_limit(), Some(30.));
+    let solution = model.solve().unwrap();
+    assert_eq!(solution.value(a), 10.);
+}
+
+#[allow(dead_code)]
+fn generic_verbosity<S>(solver: S)
+where
+    S: Solver,
+    S::Model: WithVerbosity,
+{
+    variables! { vars: 0 <= a (integer) <= 10; };
+    let model = vars.maximise(a).using(solver).with_verbosity(false);
+    assert!(!model.verbose());
+    let solution = model.solve().unwrap();
+    assert_eq!(solution.value(a), 10.);
+}
+
+#[allow(dead_code)]
+fn generic_all_options<S>(solver: S)
+where
+    S: Solver,
+    S::Model: WithTimeLimit + WithThreads + WithVerbosity + WithRandomSeed,
+{
+    variables! { vars: 0 <= a (integer) <= 10; };
+    let model = vars
+        .maximise(a)
+        .using(solver)
+        .with_time_limit(30.)
+        .with_threads(1)
+        .with_verbosity(false)
+        .with_random_seed(12345);
+    assert_eq!(model.threads(), Some(1));
+    assert!(!model.verbose());
+    assert_eq!(model.random_seed(), Some(12345));
+    let solution = model.solve().unwrap();
+    assert_eq!(solution.value(a), 10.);
+}
+
+#[cfg(feature = "coin_cbc")]
+#[test]
+fn options_coin_cbc() {
+    generic_time_limit(coin_cbc);
+    generic_all_options(coin_cbc);
+}
+
+#[cfg(feature = "highs")]
+#[test]
+fn options_highs() {
+    generic_time_limit(highs);
+    generic_all_options(highs);
+}
+
+#[cfg(feature = "minilp")]
+#[test]
+fn options_minilp() {
+    generic_time_limit(minilp);
+}
+
+#[cfg(feature = "lpsolve")]
+#[test]
+fn options_lpsolve() {
+    generic_time_limit(lp_solve);
+    generic_verbosity(lp_solve);
+}
+
+#[cfg(feature = "scip")]
+#[test]
+fn options_scip() {
+    generic_time_limit(scip);
+    generic_all_options(scip);
+}